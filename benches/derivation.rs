@@ -0,0 +1,68 @@
+//! Benchmarks for derivation primitives.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use secret_tree::{Name, SecretTree};
+
+fn create_tree() -> SecretTree {
+    SecretTree::new(&mut ChaChaRng::seed_from_u64(123))
+}
+
+fn bench_derivation(criterion: &mut Criterion) {
+    let tree = create_tree();
+    let mut group = criterion.benchmark_group("derivation");
+    group.bench_function("child", |bencher| {
+        bencher.iter(|| tree.child(Name::new("bench")));
+    });
+    group.bench_function("index", |bencher| {
+        bencher.iter(|| tree.index(42));
+    });
+    group.bench_function("digest", |bencher| {
+        let digest = [0xab; 32];
+        bencher.iter(|| tree.digest(&digest));
+    });
+    group.bench_function("fill", |bencher| {
+        bencher.iter_batched(
+            || tree.index(0),
+            |child| {
+                let mut key = [0_u8; 32];
+                child.fill(&mut key);
+                key
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    group.bench_function("rng", |bencher| {
+        bencher.iter_batched(|| tree.index(0), SecretTree::rng, BatchSize::SmallInput);
+    });
+    group.finish();
+}
+
+fn bench_many_children(criterion: &mut Criterion) {
+    const CHILD_COUNT: u64 = 10_000;
+
+    let tree = create_tree();
+    let mut group = criterion.benchmark_group("many_children");
+    group.sample_size(10);
+    group.bench_function("index_loop", |bencher| {
+        bencher.iter(|| {
+            for i in 0..CHILD_COUNT {
+                let mut key = [0_u8; 32];
+                tree.index(i).fill(&mut key);
+            }
+        });
+    });
+    group.bench_function("indexed_children", |bencher| {
+        bencher.iter(|| {
+            for (_, child) in tree.indexed_children(Name::new("accounts"), 0..CHILD_COUNT) {
+                let mut key = [0_u8; 32];
+                child.fill(&mut key);
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_derivation, bench_many_children);
+criterion_main!(benches);
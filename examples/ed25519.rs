@@ -59,8 +59,7 @@ fn main() {
     );
 
     // Assume that we have securely persisted the RNG tree (e.g., with passphrase encryption).
-    let seed = tree.seed().clone();
-    drop(tree);
+    let seed = tree.into_seed();
 
     // ...Then, we can restore all keys by deserializing the RNG tree.
     let tree = SecretTree::from_slice(seed.expose_secret()).unwrap();
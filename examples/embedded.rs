@@ -0,0 +1,67 @@
+//! Example of using `SecretTree` in a `no_std` environment, e.g., on an embedded device
+//! with a seed provisioned in the device storage.
+//!
+//! The derivation logic below only uses `core` APIs; the example should be run without
+//! default crate features:
+//!
+//! ```shell
+//! cargo run --no-default-features --example embedded
+//! ```
+
+#![no_std]
+
+// Only used to run the example on the host.
+extern crate std;
+
+use secret_tree::{Name, SecretTree};
+
+/// Seed provisioned in the device storage. In a real application, the seed must be unique
+/// for each device and stored in a protected memory region.
+const PROVISIONED_SEED: [u8; 32] = [
+    0x8d, 0x25, 0x7a, 0x4b, 0x33, 0x0b, 0x51, 0x4c, 0x2d, 0x9e, 0x60, 0x17, 0xe2, 0x8a, 0x55, 0xa1,
+    0x3b, 0x4f, 0x0c, 0x99, 0x61, 0xd8, 0x7e, 0x12, 0xf4, 0x03, 0xae, 0x66, 0x28, 0xc1, 0x5d, 0x90,
+];
+
+/// Keys used by the device firmware.
+struct DeviceKeys {
+    identity_key: [u8; 32],
+    session_keys: [[u64; 4]; 3],
+    counter_seed: u128,
+}
+
+impl DeviceKeys {
+    fn derive(seed: &[u8]) -> Self {
+        let tree = SecretTree::from_slice(seed).expect("invalid seed length");
+
+        let mut identity_key = [0; 32];
+        tree.child(Name::new("identity")).fill(&mut identity_key);
+
+        let sessions = tree.child(Name::new("sessions"));
+        let mut session_keys = [[0; 4]; 3];
+        for (i, key) in (0..).zip(&mut session_keys) {
+            sessions.index(i).fill(key);
+        }
+
+        let mut counter_seed = 0;
+        tree.child(Name::new("counter")).fill(&mut counter_seed);
+
+        Self {
+            identity_key,
+            session_keys,
+            counter_seed,
+        }
+    }
+}
+
+fn main() {
+    let keys = DeviceKeys::derive(&PROVISIONED_SEED);
+    // Derivation is deterministic, so the keys can be re-derived after a device reboot.
+    let restored_keys = DeviceKeys::derive(&PROVISIONED_SEED);
+    assert_eq!(keys.identity_key, restored_keys.identity_key);
+    assert_eq!(keys.session_keys, restored_keys.session_keys);
+    assert_eq!(keys.counter_seed, restored_keys.counter_seed);
+
+    // Keys derived from different children are unrelated.
+    assert_ne!(keys.session_keys[0], keys.session_keys[1]);
+    std::println!("Derived device keys successfully");
+}
@@ -0,0 +1,98 @@
+//! Generator of test vectors for the libsodium-compatible key derivation, in the format
+//! used by the `sodium_test_vectors_*` tests in `src/kdf.rs`. The master key is always
+//! `[0, 1, 2, .., 31]`, as in the libsodium test suite.
+//!
+//! Usage:
+//!
+//! ```shell
+//! # 64-byte outputs for subkey IDs 0..10 (`sodium_test_vectors_64byte_output`)
+//! cargo run --example gen_vectors -- fixed 64 10
+//! # Outputs with byte lengths 16, 20, .., 40, each with the subkey ID equal to the length
+//! # (`sodium_test_vectors_varying_len_output`)
+//! cargo run --example gen_vectors -- varying 16 40
+//! ```
+//!
+//! The context can be overridden with the `CONTEXT` env variable (default: `KDF test`).
+//! Outputs are produced with [`SecretTree::derive_raw()`], i.e., with the same code
+//! as the one being tested.
+
+use std::{env, fmt::Write as _, process};
+
+use secret_tree::{SecretTree, CONTEXT_LEN, SEED_LEN};
+
+/// Max number of hex digits on a single line of a multiline literal.
+const LINE_LEN: usize = 64;
+
+const USAGE: &str =
+    "Usage: gen_vectors fixed <len> <count> | gen_vectors varying <min_len> <max_len>";
+
+fn master_key() -> SecretTree {
+    let key: [u8; SEED_LEN] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+    SecretTree::from_slice(&key).unwrap()
+}
+
+fn context() -> [u8; CONTEXT_LEN] {
+    let context = env::var("CONTEXT").unwrap_or_else(|_| "KDF test".to_owned());
+    context.as_bytes().try_into().unwrap_or_else(|_| {
+        eprintln!("Context must have length {CONTEXT_LEN} bytes");
+        process::exit(1);
+    })
+}
+
+fn derive_hex(tree: &SecretTree, subkey_id: u64, context: [u8; CONTEXT_LEN], len: usize) -> String {
+    let mut output = vec![0_u8; len];
+    tree.derive_raw(subkey_id, context, &mut output);
+    output.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02x}").unwrap();
+        hex
+    })
+}
+
+/// Formats a `Hex.decode(..)` invocation for a fixed-size array literal, splitting long
+/// literals into multiple lines.
+fn format_fixed(hex: &str) -> String {
+    let lines: Vec<_> = hex
+        .as_bytes()
+        .chunks(LINE_LEN)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect();
+    let literal = lines.join("\\\n              ");
+    format!("        Hex.decode(\n            b\"{literal}\",\n        ),")
+}
+
+fn parse_arg(arg: Option<String>) -> usize {
+    let parsed = arg.as_deref().and_then(|arg| arg.parse().ok());
+    parsed.unwrap_or_else(|| {
+        eprintln!("{USAGE}");
+        process::exit(1);
+    })
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let mode = args.next();
+    let (first, second) = (parse_arg(args.next()), parse_arg(args.next()));
+    let tree = master_key();
+    let context = context();
+
+    match mode.as_deref() {
+        Some("fixed") => {
+            let (len, count) = (first, second);
+            for subkey_id in 0..count as u64 {
+                let hex = derive_hex(&tree, subkey_id, context, len);
+                println!("{}", format_fixed(&hex));
+            }
+        }
+        Some("varying") => {
+            let (min_len, max_len) = (first, second);
+            for len in (min_len..=max_len).step_by(4) {
+                let hex = derive_hex(&tree, len as u64, context, len);
+                println!("        &Hex.decode::<{len}>(b\"{hex}\"),");
+            }
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Example of building a key ring from a serializable derivation config.
+//!
+//! The config lists key domains; each domain is a named child of the root tree, with
+//! an optional derivation path relative to it and the number of indexed keys. Requires
+//! the `serde` crate feature:
+//!
+//! ```shell
+//! cargo run --features serde --example key_ring
+//! ```
+
+use rand::thread_rng;
+use secrecy::{ExposeSecret, SecretBox};
+use serde::Deserialize;
+
+use secret_tree::{Name, PathError, SecretTree, TreeId};
+
+/// Derivation config embedded into the example. In a real application, it would be loaded
+/// from a file; unlike the tree seed, the config is not secret.
+const CONFIG: &str = r#"
+[[domains]]
+name = "payments"
+path = "accounts/0"
+key_count = 3
+
+[[domains]]
+name = "messaging"
+path = "devices/phone"
+key_count = 2
+
+[[domains]]
+name = "backup"
+key_count = 1
+"#;
+
+#[derive(Debug, Deserialize)]
+struct KeyRingConfig {
+    domains: Vec<DomainConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DomainConfig {
+    /// Name of the domain; validated on deserialization.
+    name: Name,
+    /// Derivation path relative to the domain tree (may be empty).
+    #[serde(default)]
+    path: String,
+    /// Number of keys derived for the domain.
+    key_count: u64,
+}
+
+/// Keys for a single domain.
+struct DomainKeys {
+    name: Name,
+    keys: Vec<SecretBox<[u8; 32]>>,
+    /// Non-secret identifiers of the key trees; used for logging.
+    key_ids: Vec<TreeId>,
+}
+
+impl DomainKeys {
+    fn new(tree: &SecretTree, config: &DomainConfig) -> Result<Self, PathError> {
+        let domain_tree = tree.child(config.name).derive_path(&config.path)?;
+        let (keys, key_ids) = domain_tree
+            .indices(0..config.key_count)
+            .map(|(_, key_tree)| {
+                let id = key_tree.id();
+                (key_tree.create_secret(), id)
+            })
+            .unzip();
+        Ok(Self {
+            name: config.name,
+            keys,
+            key_ids,
+        })
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config: KeyRingConfig = toml::from_str(CONFIG)?;
+    let tree = SecretTree::new(&mut thread_rng());
+
+    let key_ring = config
+        .domains
+        .iter()
+        .map(|domain| DomainKeys::new(&tree, domain))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (domain, domain_keys) in config.domains.iter().zip(&key_ring) {
+        println!("Domain `{}`:", domain_keys.name);
+        for (i, (key, id)) in domain_keys
+            .keys
+            .iter()
+            .zip(&domain_keys.key_ids)
+            .enumerate()
+        {
+            println!("  key #{i}: {id}");
+
+            // Each key can be equivalently derived using a single path from the root.
+            let full_path = if domain.path.is_empty() {
+                format!("{}/{i}", domain.name)
+            } else {
+                format!("{}/{}/{i}", domain.name, domain.path)
+            };
+            let key_tree = tree.derive_path(&full_path)?;
+            assert_eq!(key_tree.id(), *id);
+            let expected_key: SecretBox<[u8; 32]> = key_tree.create_secret();
+            assert_eq!(expected_key.expose_secret(), key.expose_secret());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! `arbitrary` support for fuzzing.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Name, RawId, SecretTree, Seed, Segment, SplitHalf, MAX_NAME_LEN, SEED_LEN};
+
+/// Generates a tree with a seed consisting of 32 bytes consumed from the input. Inputs
+/// that would produce an all-zero seed are rejected, since such seeds
+/// [are considered a misuse](SecretTree::from_seed()).
+impl<'a> Arbitrary<'a> for SecretTree {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let seed_bytes: [u8; SEED_LEN] = u.arbitrary()?;
+        SecretTree::from_seed_checked(Seed::from(&seed_bytes))
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (SEED_LEN, Some(SEED_LEN))
+    }
+}
+
+/// Generates a valid name by taking the longest prefix of an arbitrary string that fits
+/// into [`MAX_NAME_LEN`] bytes, with null chars removed.
+impl<'a> Arbitrary<'a> for Name {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let raw: &str = u.arbitrary()?;
+        let mut bytes = [0_u8; MAX_NAME_LEN];
+        let mut len = 0;
+        for ch in raw.chars().filter(|&ch| ch != '\0') {
+            let ch_len = ch.len_utf8();
+            if len + ch_len > MAX_NAME_LEN {
+                break;
+            }
+            ch.encode_utf8(&mut bytes[len..]);
+            len += ch_len;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Segment {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0_u8..=7)? {
+            0 => Self::Name(u.arbitrary()?),
+            1 => Self::Index(u.arbitrary()?),
+            2 => Self::Digest(u.arbitrary()?),
+            3 => Self::Split(if u.arbitrary()? {
+                SplitHalf::Rng
+            } else {
+                SplitHalf::Keys
+            }),
+            4 => Self::Rotation(u.arbitrary()?),
+            5 => Self::Index2(u.arbitrary()?, u.arbitrary()?),
+            6 => {
+                let len = u.int_in_range(0..=RawId::MAX_LEN)?;
+                let bytes: [u8; RawId::MAX_LEN] = u.arbitrary()?;
+                let id =
+                    RawId::new(&bytes[..len]).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+                Self::Bytes(id)
+            }
+            _ => Self::Binding(u.arbitrary()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn generating_names() {
+        let data: Vec<u8> = (0..=255).cycle().take(4_096).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..100 {
+            let name = Name::arbitrary(&mut u).unwrap();
+            let name_str = name.as_ref();
+            assert!(name_str.len() <= MAX_NAME_LEN);
+            assert_eq!(Name::from_str(name_str).unwrap(), name);
+        }
+    }
+
+    #[test]
+    fn generating_trees() {
+        let data = [1_u8; 32];
+        let tree = SecretTree::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(tree, SecretTree::from_slice(&data).unwrap());
+
+        let zeros = [0_u8; 32];
+        SecretTree::arbitrary(&mut Unstructured::new(&zeros)).unwrap_err();
+        let _ = Segment::arbitrary(&mut Unstructured::new(&data)).unwrap();
+    }
+}
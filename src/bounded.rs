@@ -0,0 +1,133 @@
+//! Trees with a declared capacity for indexed children.
+
+use core::fmt;
+
+use crate::SecretTree;
+
+/// Error returned by [`BoundedTree::index()`] if the index is out of bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CapacityError {
+    /// Requested index.
+    pub index: u64,
+    /// Declared capacity of the tree.
+    pub capacity: u64,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "index {} is out of bounds for tree with capacity {}",
+            self.index, self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// [`SecretTree`] wrapper with a declared capacity for indexed children, e.g. for
+/// a fixed-capacity key ring. Created via [`SecretTree::bounded()`].
+///
+/// The capacity is a safety rail only: it does not influence derivation, so
+/// [`Self::index()`] returns the same children as [`SecretTree::index()`].
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{Name, SecretTree};
+/// # use rand::thread_rng;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let key_ring = tree.child(Name::new("keys")).bounded(16);
+/// let key = key_ring.index(15)?;
+/// assert_eq!(key, tree.child(Name::new("keys")).index(15));
+/// assert!(key_ring.index(16).is_err());
+/// # Ok::<_, secret_tree::CapacityError>(())
+/// ```
+#[derive(Debug)]
+#[must_use]
+pub struct BoundedTree {
+    inner: SecretTree,
+    capacity: u64,
+}
+
+impl BoundedTree {
+    /// Returns the declared capacity of this tree.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Returns a reference to the wrapped tree.
+    pub fn as_tree(&self) -> &SecretTree {
+        &self.inner
+    }
+
+    /// Unwraps the underlying tree.
+    pub fn into_inner(self) -> SecretTree {
+        self.inner
+    }
+
+    /// Produces a child with the specified integer index, checking that the index is less
+    /// than the declared capacity. The child is the same as produced by [`SecretTree::index()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index >= capacity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](SecretTree::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    pub fn index(&self, index: u64) -> Result<SecretTree, CapacityError> {
+        if index >= self.capacity {
+            return Err(CapacityError {
+                index,
+                capacity: self.capacity,
+            });
+        }
+        Ok(self.inner.index(index))
+    }
+}
+
+impl SecretTree {
+    /// Wraps this tree, declaring the capacity for its indexed children.
+    /// See [`BoundedTree`] for details.
+    pub fn bounded(self, capacity: u64) -> BoundedTree {
+        BoundedTree {
+            inner: self,
+            capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_tree_basics() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let bounded = SecretTree::from_slice(&[7; 32]).unwrap().bounded(3);
+        assert_eq!(bounded.capacity(), 3);
+        for i in 0..3 {
+            assert_eq!(bounded.index(i).unwrap(), tree.index(i));
+        }
+        let err = bounded.index(3).unwrap_err();
+        assert_eq!(
+            err,
+            CapacityError {
+                index: 3,
+                capacity: 3
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "index 3 is out of bounds for tree with capacity 3"
+        );
+        assert_eq!(bounded.into_inner(), tree);
+
+        let empty = tree.bounded(0);
+        assert!(empty.index(0).is_err());
+    }
+}
@@ -2,11 +2,22 @@
 
 use core::{mem, slice};
 
+use secrecy::zeroize::{Zeroize, Zeroizing};
+
+use crate::{FillError, SecretTree};
+
 /// Converts a type to a mutable byte slice. This is used within the crate to fill
 /// secret values with the RNG output.
 ///
-/// This trait is implemented for numeric types (`u8`, `i8`, ..., `u128`, `i128`), slices
-/// of these types, and arrays of small sizes (1..=64).
+/// This trait is implemented for numeric types (`u8`, `i8`, ..., `u128`, `i128`, `f32`, `f64`),
+/// slices of these types, arrays (including nested arrays, such as `[[u8; 8]; 4]`), and
+/// [`Zeroizing`] wrappers around the supported types. Nested arrays are filled as if they
+/// were flattened, so e.g. `[[u8; 8]; 4]` is filled in the same way as `[u8; 32]`.
+/// With the `heapless` feature, the trait is also implemented for `heapless::Vec`.
+///
+/// Floating-point values are filled with arbitrary bit patterns, which are not uniformly
+/// distributed and may include infinities and NaNs. Such values should be post-processed
+/// (e.g., via `f64::to_bits()` and mapping the bits to the desired distribution).
 // This is an ad-hoc replacement for the eponymous trait from `rand` v0.7, which was removed
 // in `rand` v0.8.
 pub trait AsByteSliceMut {
@@ -60,6 +71,34 @@ macro_rules! impl_as_byte_slice {
 
 impl_as_byte_slice!(i8, u16, i16, u32, i32, u64, i64, u128, i128,);
 
+// Floating-point values are filled with raw bit patterns; endianness is normalized
+// on the bit representation.
+macro_rules! impl_as_byte_slice_for_float {
+    ($($ty:ty,)*) => {
+        $(
+        impl AsByteSliceMut for [$ty] {
+            fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+                if self.is_empty() {
+                    &mut []
+                } else {
+                    let byte_len = self.len() * mem::size_of::<$ty>();
+                    let data = (self as *mut [$ty]).cast::<u8>();
+                    unsafe { slice::from_raw_parts_mut(data, byte_len) }
+                }
+            }
+
+            fn convert_to_le(&mut self) {
+                for element in self {
+                    *element = <$ty>::from_bits(element.to_bits().to_le());
+                }
+            }
+        }
+        )*
+    };
+}
+
+impl_as_byte_slice_for_float!(f32, f64,);
+
 impl<T> AsByteSliceMut for T
 where
     [T]: AsByteSliceMut,
@@ -73,15 +112,154 @@ where
     }
 }
 
-impl<T, const N: usize> AsByteSliceMut for [T; N]
+/// Allows filling slices of arrays. Together with the blanket implementation above, this covers
+/// arrays (including nested ones, such as `[[u8; 4]; 4]`) and slices of arrays.
+impl<T, const M: usize> AsByteSliceMut for [[T; M]]
 where
     [T]: AsByteSliceMut,
 {
     fn as_byte_slice_mut(&mut self) -> &mut [u8] {
-        AsByteSliceMut::as_byte_slice_mut(&mut self[..])
+        AsByteSliceMut::as_byte_slice_mut(flatten(self))
     }
 
     fn convert_to_le(&mut self) {
-        AsByteSliceMut::convert_to_le(&mut self[..]);
+        AsByteSliceMut::convert_to_le(flatten(self));
+    }
+}
+
+// Replacement for `<[[T; M]]>::as_flattened_mut()`, which requires Rust 1.80.
+fn flatten<T, const M: usize>(slice: &mut [[T; M]]) -> &mut [T] {
+    let len = slice.len() * M;
+    // SAFETY: `[T; M]` has the same layout as `M` consecutive `T`s, and the pointer
+    // is non-null and properly aligned even if `len == 0`.
+    unsafe { slice::from_raw_parts_mut(slice.as_mut_ptr().cast::<T>(), len) }
+}
+
+/// Allows filling buffers that are zeroized on drop, such as `Zeroizing<[u8; 32]>`.
+impl<T> AsByteSliceMut for Zeroizing<T>
+where
+    T: AsByteSliceMut + Zeroize,
+{
+    fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        AsByteSliceMut::as_byte_slice_mut(&mut **self)
+    }
+
+    fn convert_to_le(&mut self) {
+        AsByteSliceMut::convert_to_le(&mut **self);
+    }
+}
+
+/// Allows filling fixed-capacity vectors, e.g., on embedded targets without a global allocator.
+/// The filled byte length is determined by the current vector length, not by its capacity.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::SecretTree;
+/// # use rand::thread_rng;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let mut key = heapless::Vec::<u8, 64>::new();
+/// key.resize(32, 0).unwrap();
+/// tree.fill(&mut key);
+/// ```
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> AsByteSliceMut for heapless::Vec<T, N>
+where
+    [T]: AsByteSliceMut,
+{
+    fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        AsByteSliceMut::as_byte_slice_mut(self.as_mut_slice())
+    }
+
+    fn convert_to_le(&mut self) {
+        AsByteSliceMut::convert_to_le(self.as_mut_slice());
+    }
+}
+
+/// Adapter bridging [`AsByteSliceMut`] types to the [`rand::Fill`] trait from `rand` v0.8,
+/// so that they can be filled with [`Rng::fill()`](rand::Rng::fill()) (e.g., using the RNG
+/// returned by [`SecretTree::rng()`]).
+///
+/// A blanket `rand::Fill` implementation for all `AsByteSliceMut` types is impossible because
+/// of Rust coherence rules, hence the wrapper. For types supported by both traits
+/// (e.g., `[u32; 4]`), filling via the adapter produces the same result as `rand::Fill`:
+/// the buffer is filled with RNG bytes, and then converted to the little-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// # use rand::{thread_rng, Rng};
+/// # use secret_tree::{FillAdapter, SecretTree};
+/// let mut rng = SecretTree::new(&mut thread_rng()).rng();
+/// let mut floats = [0_f32; 8];
+/// rng.fill(&mut FillAdapter(&mut floats));
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug)]
+pub struct FillAdapter<'a, T: ?Sized>(pub &'a mut T);
+
+#[cfg(feature = "rand")]
+impl<T: AsByteSliceMut + ?Sized> rand::Fill for FillAdapter<'_, T> {
+    fn try_fill<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), rand::Error> {
+        rng.try_fill_bytes(self.0.as_byte_slice_mut())?;
+        self.0.convert_to_le();
+        Ok(())
+    }
+}
+
+/// Tuple of mutable references to buffers that can be filled with
+/// [`SecretTree::fill_tuple()`]. Implemented for tuples with 1..=8 elements, each of which
+/// is a mutable reference to a type implementing [`AsByteSliceMut`].
+pub trait FillTuple {
+    /// Fills the buffers in this tuple with keys derived from the indexed children
+    /// of `tree`; the buffer at position `i` uses index `i`.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same cases as [`SecretTree::try_fill_many()`].
+    fn try_fill_from(self, tree: &SecretTree) -> Result<(), FillError>;
+}
+
+macro_rules! impl_fill_tuple {
+    ($($ty:ident : $i:tt),+) => {
+        impl<'a, $($ty: AsByteSliceMut,)+> FillTuple for ($(&'a mut $ty,)+) {
+            fn try_fill_from(self, tree: &SecretTree) -> Result<(), FillError> {
+                tree.try_fill_many([$(($i, self.$i as &mut dyn AsByteSliceMut),)+])
+            }
+        }
+    };
+}
+
+impl_fill_tuple!(T0: 0);
+impl_fill_tuple!(T0: 0, T1: 1);
+impl_fill_tuple!(T0: 0, T1: 1, T2: 2);
+impl_fill_tuple!(T0: 0, T1: 1, T2: 2, T3: 3);
+impl_fill_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4);
+impl_fill_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5);
+impl_fill_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6);
+impl_fill_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7);
+
+#[cfg(all(test, feature = "rand"))]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    use super::*;
+
+    #[test]
+    fn fill_adapter_is_consistent_with_rand() {
+        let mut rng = ChaChaRng::seed_from_u64(123);
+        let mut expected = [0_u32; 8];
+        rng.fill(&mut expected);
+        let mut expected_bytes = [0_u8; 24];
+        rng.fill(&mut expected_bytes);
+
+        let mut rng = ChaChaRng::seed_from_u64(123);
+        let mut buffer = [0_u32; 8];
+        rng.fill(&mut FillAdapter(&mut buffer));
+        assert_eq!(buffer, expected);
+        let mut bytes = [0_u8; 24];
+        rng.fill(&mut FillAdapter(&mut bytes[..]));
+        assert_eq!(bytes, expected_bytes);
     }
 }
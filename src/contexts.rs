@@ -0,0 +1,108 @@
+//! Blake2b personalizations (aka contexts) used by the crate for derivation.
+//!
+//! These values are a part of the derivation contract: changing any of them would change
+//! all secrets derived with the corresponding method. They are exposed to simplify
+//! reproducing derivations in other environments; see the [crate-level docs](crate#salt-and-personalization)
+//! for the salts paired with each personalization.
+
+use crate::CONTEXT_LEN;
+
+/// Personalization for [secret keys](crate::SecretTree::fill()).
+pub const FILL_BYTES: [u8; CONTEXT_LEN] = *b"bytes\0\0\0";
+/// Personalization for [small secret keys](crate::SecretTree::fill_small()) (1..=15 bytes).
+pub const FILL_SMALL_BYTES: [u8; CONTEXT_LEN] = *b"small\0\0\0";
+/// Personalization for [large secret keys](crate::SecretTree::fill_xof()).
+pub const XOF: [u8; CONTEXT_LEN] = *b"xof\0\0\0\0\0";
+/// Personalization for [CSPRNG](crate::SecretTree::rng()) seeds.
+pub const RNG: [u8; CONTEXT_LEN] = *b"rng\0\0\0\0\0";
+/// Personalization for seeds of [named children](crate::SecretTree::child()).
+pub const NAME: [u8; CONTEXT_LEN] = *b"name\0\0\0\0";
+/// Personalization for seeds of [children identified by bytes](crate::SecretTree::child_raw()).
+pub const RAW: [u8; CONTEXT_LEN] = *b"raw\0\0\0\0\0";
+/// Personalization for seeds of [indexed children](crate::SecretTree::index()).
+pub const INDEX: [u8; CONTEXT_LEN] = *b"index\0\0\0";
+/// Personalization for seeds of [2D-indexed children](crate::SecretTree::index2()).
+pub const INDEX2: [u8; CONTEXT_LEN] = *b"index2\0\0";
+/// Personalization for the first iteration of deriving a [digest child](crate::SecretTree::digest()).
+pub const DIGEST_START: [u8; CONTEXT_LEN] = *b"digest0\0";
+/// Personalization for the second iteration of deriving a digest child.
+pub const DIGEST_END: [u8; CONTEXT_LEN] = *b"digest1\0";
+/// Personalization for seeds of [children bound to associated data](crate::SecretTree::bind()).
+pub const BINDING: [u8; CONTEXT_LEN] = *b"bind\0\0\0\0";
+/// Personalization for [stretching](crate::SecretTree::from_short_seed()) short seeds.
+pub const STRETCH: [u8; CONTEXT_LEN] = *b"stretch\0";
+/// Personalization for seeds of [split children](crate::SecretTree::split()).
+pub const SPLIT: [u8; CONTEXT_LEN] = *b"split\0\0\0";
+/// Personalization for [rotated trees](crate::SecretTree::rotate()).
+pub const ROTATION: [u8; CONTEXT_LEN] = *b"rotate\0\0";
+/// Personalization for [info-parameterized keys](crate::SecretTree::try_derive_info()).
+pub const INFO: [u8; CONTEXT_LEN] = *b"info\0\0\0\0";
+/// Personalization for AEAD keys (`SecretTree::into_cipher_material()`, available with
+/// the `aead` feature).
+pub const AEAD_KEY: [u8; CONTEXT_LEN] = *b"aeadkey\0";
+/// Personalization for AEAD nonces (`SecretTree::into_cipher_material()`, available with
+/// the `aead` feature).
+pub const AEAD_NONCE: [u8; CONTEXT_LEN] = *b"aeadnonc";
+/// Personalization for [tree IDs](crate::SecretTree::id()).
+pub const TREE_ID: [u8; CONTEXT_LEN] = *b"tree-id\0";
+/// Personalization for SLIP-0010 seeds (`SecretTree::slip10_master()`, available with
+/// the `slip10` feature).
+pub const SLIP10_SEED: [u8; CONTEXT_LEN] = *b"slip10\0\0";
+/// Personalization for seeds of trees [narrowed](crate::SecretTree64::into_tree())
+/// from 64-byte seeds.
+pub const NARROW: [u8; CONTEXT_LEN] = *b"narrow\0\0";
+/// Personalization for test seeds (`SecretTree::from_test_seed()`, available with
+/// the `testing` feature).
+pub const TEST_SEED: [u8; CONTEXT_LEN] = *b"testseed";
+
+/// All personalizations used by the crate. Custom contexts, e.g., ones supplied to
+/// [`SecretTree::derive_raw()`](crate::SecretTree::derive_raw()), must not coincide with
+/// any of these values.
+pub const ALL: [[u8; CONTEXT_LEN]; 21] = [
+    FILL_BYTES,
+    FILL_SMALL_BYTES,
+    XOF,
+    RNG,
+    NAME,
+    RAW,
+    INDEX,
+    INDEX2,
+    DIGEST_START,
+    DIGEST_END,
+    BINDING,
+    STRETCH,
+    SPLIT,
+    ROTATION,
+    INFO,
+    AEAD_KEY,
+    AEAD_NONCE,
+    TREE_ID,
+    SLIP10_SEED,
+    NARROW,
+    TEST_SEED,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contexts_are_distinct() {
+        for (i, context) in ALL.iter().enumerate() {
+            assert!(
+                !ALL[..i].contains(context),
+                "duplicate context: {context:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn contexts_are_zero_padded_ascii() {
+        for context in ALL {
+            let len = context.iter().position(|&b| b == 0).unwrap_or(CONTEXT_LEN);
+            assert!(len > 0);
+            assert!(context[..len].iter().all(u8::is_ascii_graphic));
+            assert!(context[len..].iter().all(|&b| b == 0));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Typed indices tagged with a derivation domain.
+
+use crate::{Name, SecretTree};
+
+/// Derivation domain of a typed index, such as an account ID or a device ID.
+///
+/// The domain is folded into derivation as a named child, so that indices from different
+/// domains produce different children even if their numeric values coincide.
+/// Implement this trait for newtypes wrapping numeric IDs and use them with
+/// [`SecretTree::index_typed()`] to get type-level protection against swapping indices
+/// from different domains.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{DerivationDomain, Name, SecretTree};
+/// # use rand::thread_rng;
+/// #[derive(Debug, Clone, Copy)]
+/// struct AccountId(u32);
+///
+/// impl From<AccountId> for u64 {
+///     fn from(id: AccountId) -> Self {
+///         id.0.into()
+///     }
+/// }
+///
+/// impl DerivationDomain for AccountId {
+///     const DOMAIN: Name = Name::new("account");
+/// }
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct DeviceId(u64);
+///
+/// impl From<DeviceId> for u64 {
+///     fn from(id: DeviceId) -> Self {
+///         id.0
+///     }
+/// }
+///
+/// impl DerivationDomain for DeviceId {
+///     const DOMAIN: Name = Name::new("device");
+/// }
+///
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let account_tree = tree.index_typed(AccountId(5));
+/// let device_tree = tree.index_typed(DeviceId(5));
+/// assert_ne!(account_tree, device_tree);
+/// assert_eq!(account_tree, tree.child(Name::new("account")).index(5));
+/// ```
+pub trait DerivationDomain {
+    /// Name of the domain. Used to derive a named child, which is then indexed.
+    const DOMAIN: Name;
+}
+
+impl SecretTree {
+    /// Produces a child with the specified typed index. This is equivalent to
+    /// `self.child(T::DOMAIN).index(index.into())`; in particular, the returned tree
+    /// is 2 levels deeper than this tree. See [`DerivationDomain`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn index_typed<T: Into<u64> + DerivationDomain>(&self, index: T) -> Self {
+        self.child(T::DOMAIN).index(index.into())
+    }
+}
@@ -0,0 +1,67 @@
+//! Non-secret tree identifiers.
+
+use core::fmt;
+
+/// Opaque non-secret fingerprint of a [`SecretTree`](crate::SecretTree), which can be used
+/// to correlate trees (e.g., in logs) without exposing their seeds.
+///
+/// The fingerprint is 16 bytes derived from the tree seed with keyed Blake2b using
+/// a dedicated personalization (see the [crate docs](crate) for details).
+/// Recovering the seed from the fingerprint is computationally infeasible.
+/// On the other hand, the same seed always produces the same fingerprint, regardless
+/// of the derivation path of the tree.
+///
+/// `TreeId` is displayed as a hex string:
+///
+/// ```
+/// # use secret_tree::{SecretTree, TreeId};
+/// # use rand::thread_rng;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let id: TreeId = tree.id();
+/// assert_eq!(id.to_string().len(), 32);
+/// println!("Using tree {id}");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TreeId([u8; 16]);
+
+impl TreeId {
+    /// Creates an ID from its byte representation, e.g., one published by another party.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the byte representation of this ID.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for TreeId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "TreeId({self})")
+    }
+}
+
+impl fmt::Display for TreeId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(formatter, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id_formatting() {
+        let id = TreeId::from_bytes([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0xa, 0xb, 0xc, 0xd, 0xe, 0xff]);
+        assert_eq!(id.to_string(), "000102030405060708090a0b0c0d0eff");
+        assert_eq!(
+            format!("{id:?}"),
+            "TreeId(000102030405060708090a0b0c0d0eff)"
+        );
+    }
+}
@@ -1,5 +1,16 @@
 //! `libsodium`-compatible generic key derivation.
+//!
+//! Derivation is keyed Blake2b with an empty message, with salt and personalization set
+//! in the initialization block. The *key length* parameter in the initialization block
+//! is set to the actual key length, as prescribed by the Blake2b spec. With a 32-byte key
+//! (which is the only key length used for `SecretTree` seeds), this is exactly
+//! `crypto_kdf_derive_from_key` from libsodium. libsodium does not support master keys
+//! of other lengths in its KDF API; keys of other lengths (used, e.g., when stretching
+//! short seeds) correspond to `crypto_generichash_blake2b_salt_personal` with an empty message.
 
+use core::mem::MaybeUninit;
+
+#[cfg(any(not(feature = "simd"), test))]
 use blake2::{
     digest::{
         core_api::{Buffer, UpdateCore, VariableOutputCore},
@@ -7,6 +18,7 @@ use blake2::{
     },
     Blake2bVarCore,
 };
+use secrecy::zeroize::Zeroize;
 
 use crate::FillError;
 
@@ -16,15 +28,51 @@ use crate::FillError;
 // is the size of the `ChaChaRng` seed.
 pub const SEED_LEN: usize = 32;
 
-/// Byte length of a context variable.
+/// Byte length of a context (aka personalization) used in key derivation (8).
 // This length is half of what is supported by Blake2b (16 bytes),
 // but is compatible with the key derivation in `libsodium`. We don’t
-// need more internally and do not expose context to users.
-pub(crate) const CONTEXT_LEN: usize = 8;
+// need more internally; users can only supply contexts in the libsodium-compatible
+// `SecretTree::derive_raw()`.
+pub const CONTEXT_LEN: usize = 8;
 
 /// Byte length of salt in the Blake2b initialization block.
 pub(crate) const SALT_LEN: usize = 16;
 
+/// Maximum key length supported by Blake2b.
+pub(crate) const MAX_KEY_LEN: usize = 64;
+
+/// Maximum output length supported by Blake2b.
+pub(crate) const MAX_OUTPUT_LEN: usize = 64;
+
+/// Minimum output length supported by libsodium.
+pub(crate) const MIN_SUPPORTED_OUTPUT_LEN: usize = 16;
+
+/// Byte length of a Blake2b block.
+const BLOCK_LEN: usize = 128;
+
+/// Hashes arbitrary-length data into a salt using unkeyed Blake2b-128 (no salt or personalization).
+pub(crate) fn hash_to_salt(data: &[u8]) -> [u8; SALT_LEN] {
+    use blake2::{
+        digest::{consts::U16, Digest},
+        Blake2b,
+    };
+
+    Blake2b::<U16>::digest(data).into()
+}
+
+/// Returns the number of Blake2b compression function invocations (i.e., processed 128-byte
+/// blocks) required for a single derivation, such as deriving a secret key or a child seed.
+///
+/// A derivation is a keyed Blake2b hash with an empty message. The key (the tree seed)
+/// is padded with zeros to a full block, and this block is the only one processed; thus,
+/// the returned value is 1. The cost does not depend on the output length. Note that some
+/// operations perform several derivations; e.g., deriving a [digest child](crate::SecretTree::digest())
+/// takes 2 derivations.
+pub const fn derivation_block_count() -> usize {
+    const _: () = assert!(MAX_KEY_LEN <= BLOCK_LEN);
+    1
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Index {
     None,
@@ -46,50 +94,132 @@ impl Index {
     }
 }
 
+/// Derives a key into `output`. `key` must have length `1..=MAX_KEY_LEN`; this is not checked
+/// in release builds.
 pub(crate) fn try_derive_key(
     output: &mut [u8],
     index: Index,
     context: [u8; CONTEXT_LEN],
-    key: &[u8; SEED_LEN],
+    key: &[u8],
+) -> Result<(), FillError> {
+    check_key_len(output.len())?;
+    derive_key_unchecked(output, index, context, key);
+    Ok(())
+}
+
+/// Derives a short key (`1..=15` bytes) into `output`. Such lengths are supported by Blake2b,
+/// but not by libsodium.
+pub(crate) fn try_derive_short_key(
+    output: &mut [u8],
+    index: Index,
+    context: [u8; CONTEXT_LEN],
+    key: &[u8],
 ) -> Result<(), FillError> {
-    const MIN_SUPPORTED_SIZE: usize = 16;
-    const MAX_SUPPORTED_SIZE: usize = 64;
+    check_output_len(output.len(), 1, MIN_SUPPORTED_OUTPUT_LEN - 1)?;
+    derive_key_unchecked(output, index, context, key);
+    Ok(())
+}
+
+/// Checks that a key with the specified byte length can be derived with [`try_derive_key()`].
+pub(crate) fn check_key_len(len: usize) -> Result<(), FillError> {
+    check_output_len(len, MIN_SUPPORTED_OUTPUT_LEN, MAX_OUTPUT_LEN)
+}
 
-    if output.len() < MIN_SUPPORTED_SIZE {
+fn check_output_len(len: usize, min_len: usize, max_len: usize) -> Result<(), FillError> {
+    if len < min_len {
         return Err(FillError::BufferTooSmall {
-            size: output.len(),
-            min_supported_size: MIN_SUPPORTED_SIZE,
+            size: len,
+            min_supported_size: min_len,
         });
     }
-    if output.len() > MAX_SUPPORTED_SIZE {
+    if len > max_len {
         return Err(FillError::BufferTooLarge {
-            size: output.len(),
-            max_supported_size: MAX_SUPPORTED_SIZE,
+            size: len,
+            max_supported_size: max_len,
         });
     }
+    Ok(())
+}
 
+/// Performs derivation assuming that `output` has length `1..=MAX_OUTPUT_LEN`.
+fn derive_key_unchecked(output: &mut [u8], index: Index, context: [u8; CONTEXT_LEN], key: &[u8]) {
+    debug_assert!(!output.is_empty() && output.len() <= MAX_OUTPUT_LEN);
+    debug_assert!(!key.is_empty() && key.len() <= MAX_KEY_LEN);
+
+    #[cfg(not(feature = "simd"))]
+    derive_key_portable(output, index, context, key);
+    #[cfg(feature = "simd")]
+    derive_key_simd(output, index, context, key);
+}
+
+/// Performs derivation using the portable Blake2b implementation from the `blake2` crate.
+#[cfg(any(not(feature = "simd"), test))]
+fn derive_key_portable(output: &mut [u8], index: Index, context: [u8; CONTEXT_LEN], key: &[u8]) {
     let mut buffer = Buffer::<Blake2bVarCore>::default();
-    let mut core =
-        Blake2bVarCore::new_with_params(&index.to_salt(), &context, SEED_LEN, output.len());
+    // The Blake2b state is derived from the key, so it is zeroized after use. `Blake2bVarCore`
+    // does not implement `Zeroize`, hence wrapping it in `MaybeUninit`.
+    let mut core_container = MaybeUninit::new(Blake2bVarCore::new_with_params(
+        &index.to_salt(),
+        &context,
+        key.len(),
+        output.len(),
+    ));
+    // SAFETY: `core_container` is initialized above and is only zeroized after the last use
+    // of `core`.
+    let core = unsafe { core_container.assume_init_mut() };
     buffer.digest_blocks(key, |blocks| core.update_blocks(blocks));
-    // Pad the key with 3 * 32 = 96 bytes so that it occupies 2 entire blocks
-    buffer.digest_blocks(&[0; 3 * SEED_LEN], |blocks| core.update_blocks(blocks));
+    // Pad the key with zeros so that it occupies an entire block (e.g., with 3 * 32 = 96 bytes
+    // for a 32-byte key).
+    buffer.digest_blocks(&[0; BLOCK_LEN][key.len()..], |blocks| {
+        core.update_blocks(blocks);
+    });
 
     let mut full_output = Output::<Blake2bVarCore>::default();
     core.finalize_variable_core(&mut buffer, &mut full_output);
     output.copy_from_slice(&full_output[..output.len()]);
-    Ok(())
+    // `full_output` contains the derived key; don't leave it on stack.
+    full_output.as_mut_slice().zeroize();
+    // The buffer still contains the padded key block, and the core contains the final state.
+    buffer.pad_with_zeros().as_mut_slice().zeroize();
+    core_container.zeroize();
 }
 
-pub(crate) fn derive_key(
-    output: &mut [u8],
-    index: Index,
-    context: [u8; CONTEXT_LEN],
-    key: &[u8; SEED_LEN],
-) {
+/// Performs derivation using the SIMD-accelerated Blake2b implementation
+/// from the `blake2b_simd` crate.
+#[cfg(feature = "simd")]
+fn derive_key_simd(output: &mut [u8], index: Index, context: [u8; CONTEXT_LEN], key: &[u8]) {
+    // All `blake2b_simd` types involved in derivation contain secret data (the padded key block
+    // or the derived key), but do not implement `Zeroize`, hence wrapping them in `MaybeUninit`
+    // as in `derive_key_portable()`. Copies of the Blake2b state created internally
+    // by the backend during finalization cannot be wiped.
+    let mut params_container = MaybeUninit::new(blake2b_simd::Params::new());
+    // SAFETY: all containers are initialized on creation and are only zeroized after
+    // the last use of the corresponding references.
+    let params = unsafe { params_container.assume_init_mut() };
+    params
+        .hash_length(output.len())
+        .key(key)
+        .salt(&index.to_salt())
+        .personal(&context);
+    // The key block is processed during finalization, so the message is empty.
+    let mut state_container = MaybeUninit::new(params.to_state());
+    // SAFETY: see above.
+    let state = unsafe { state_container.assume_init_ref() };
+    let mut hash_container = MaybeUninit::new(state.finalize());
+    // SAFETY: see above.
+    let hash = unsafe { hash_container.assume_init_ref() };
+    output.copy_from_slice(hash.as_bytes());
+
+    hash_container.zeroize();
+    state_container.zeroize();
+    params_container.zeroize();
+}
+
+pub(crate) fn derive_key(output: &mut [u8], index: Index, context: [u8; CONTEXT_LEN], key: &[u8]) {
     try_derive_key(output, index, context, key).unwrap();
 }
 
+// Test vectors can be regenerated with `cargo run --example gen_vectors -- fixed 64 10`.
 #[test]
 fn sodium_test_vectors_64byte_output() {
     use const_decoder::Decoder::Hex;
@@ -151,6 +281,7 @@ fn sodium_test_vectors_64byte_output() {
     }
 }
 
+// Test vectors can be regenerated with `cargo run --example gen_vectors -- varying 16 40`.
 #[test]
 fn sodium_test_vectors_varying_len_output() {
     use const_decoder::Decoder::Hex;
@@ -183,3 +314,86 @@ fn sodium_test_vectors_varying_len_output() {
         assert_eq!(output.as_slice(), exp);
     }
 }
+
+#[test]
+fn key_length_is_included_into_init_block() {
+    const CTX: [u8; CONTEXT_LEN] = *b"KDF test";
+
+    let short_key = [1_u8; 16];
+    let mut padded_key = [0_u8; SEED_LEN];
+    padded_key[..16].copy_from_slice(&short_key);
+
+    // Since the key is zero-padded to the block size, the only difference between these
+    // derivations is the key length parameter.
+    let mut output = [0_u8; 32];
+    derive_key(&mut output, Index::None, CTX, &short_key);
+    let mut padded_output = [0_u8; 32];
+    derive_key(&mut padded_output, Index::None, CTX, &padded_key);
+    assert_ne!(output, padded_output);
+}
+
+#[test]
+fn short_derivation_is_keyed_blake2b() {
+    use blake2::{
+        digest::{consts::U12, Mac},
+        Blake2bMac,
+    };
+
+    const CTX: [u8; CONTEXT_LEN] = *b"KDF test";
+
+    let key = [42_u8; SEED_LEN];
+    let mut output = [0_u8; 12];
+    try_derive_short_key(&mut output, Index::None, CTX, &key).unwrap();
+    let mac = Blake2bMac::<U12>::new_with_salt_and_personal(&key, &[], &CTX).unwrap();
+    let expected = mac.finalize().into_bytes();
+    assert_eq!(output, expected.as_slice());
+
+    let mut output = [0_u8; 16];
+    let err = try_derive_short_key(&mut output, Index::None, CTX, &key).unwrap_err();
+    assert!(matches!(
+        err,
+        FillError::BufferTooLarge {
+            size: 16,
+            max_supported_size: 15
+        }
+    ));
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn simd_backend_produces_same_outputs_as_portable_backend() {
+    const CONTEXTS: [[u8; CONTEXT_LEN]; 2] = [*b"KDF test", [0; CONTEXT_LEN]];
+
+    let key: [u8; MAX_KEY_LEN] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+    let indices = [
+        Index::None,
+        Index::Number(42),
+        Index::Bytes([0xa5; SALT_LEN]),
+    ];
+    for key_len in [1, 16, SEED_LEN, 33, MAX_KEY_LEN] {
+        let key = &key[..key_len];
+        for output_len in [
+            1,
+            12,
+            MIN_SUPPORTED_OUTPUT_LEN,
+            SEED_LEN,
+            48,
+            MAX_OUTPUT_LEN,
+        ] {
+            for index in indices {
+                for context in CONTEXTS {
+                    let mut output = [0_u8; MAX_OUTPUT_LEN];
+                    let output = &mut output[..output_len];
+                    derive_key_simd(output, index, context, key);
+                    let mut expected = [0_u8; MAX_OUTPUT_LEN];
+                    let expected = &mut expected[..output_len];
+                    derive_key_portable(expected, index, context, key);
+                    assert_eq!(
+                        output, expected,
+                        "key_len={key_len}, output_len={output_len}"
+                    );
+                }
+            }
+        }
+    }
+}
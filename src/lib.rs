@@ -19,12 +19,142 @@
 //! The crate is `no_std`-compatible. There is optional `std` support enabled via the `std` feature,
 //! which is on by default.
 //!
+//! ## `alloc`
+//!
+//! *(On by default; implied by `std`)*
+//!
+//! Enables functionality requiring a global allocator.
+//!
+//! ## `rng`
+//!
+//! *(On by default)*
+//!
+//! Enables CSPRNGs derived from trees, such as `SecretTree::rng()`, and the dependency
+//! on [`rand_chacha`](https://docs.rs/rand_chacha/) that provides the CSPRNG implementation.
+//! Core derivation (children, [`SecretTree::fill()`] etc.) is available without this feature,
+//! so it can be disabled to reduce the dependency tree, e.g., for embedded targets.
+//! Outputs larger than 64 bytes can be derived without this feature
+//! using [`SecretTree::fill_xof()`].
+//!
+//! ## `track-path`
+//!
+//! *(Off by default)*
+//!
+//! Makes each [`SecretTree`] carry its derivation path relative to the root, which is accessible
+//! via `SecretTree::path()`. The path is purely diagnostic metadata; it does not influence
+//! derivation in any way.
+//!
+//! ## `subtle`
+//!
+//! *(Off by default)*
+//!
+//! Implements constant-time comparisons from the [`subtle`](https://docs.rs/subtle/) crate,
+//! such as [`ConstantTimeEq`] for [`Name`] and `CtSeed` (a constant-time comparable [`Seed`]
+//! wrapper), and constant-time selection of trees via `SecretTree::conditional_select()`.
+//!
+//! ## `serde`
+//!
+//! *(Off by default)*
+//!
+//! Implements (de)serialization of non-secret types via [`serde`](https://docs.rs/serde/).
+//! For example, a [`Name`] is serialized as its string form.
+//! The only secret type supporting (de)serialization is `SecretTreeSet`
+//! (requires the `alloc` feature), which is serialized as a map from domains to hex-encoded seeds.
+//!
+//! ## `rand`
+//!
+//! *(Off by default)*
+//!
+//! Enables helpers based on the [`rand`](https://docs.rs/rand/) crate (implies the `rng` feature), such as
+//! [`SecretTree::shuffle()`] and `FillAdapter` (which allows filling buffers via `rand::Rng::fill()`).
+//!
+//! ## `rand09`
+//!
+//! *(Off by default)*
+//!
+//! Enables interoperability with `rand_core` v0.9 and `rand_chacha` v0.9
+//! (the crate itself uses `rand_core` v0.6), such as [`SecretTree::new_v09()`]
+//! and [`SecretTree::rng_v09()`]. RNGs derived using both versions produce identical output.
+//!
+//! ## `arbitrary`
+//!
+//! *(Off by default)*
+//!
+//! Implements [`Arbitrary`](https://docs.rs/arbitrary/) for [`SecretTree`], [`Name`]
+//! and [`Segment`], which is useful for fuzzing code consuming these types.
+//!
+//! ## `ed25519`
+//!
+//! *(Off by default)*
+//!
+//! Provides `SecretTree::into_ed25519_keypair()` to derive Ed25519 keypairs
+//! from [`ed25519-dalek`](https://docs.rs/ed25519-dalek/).
+//!
+//! ## `heapless`
+//!
+//! *(Off by default)*
+//!
+//! Implements [`AsByteSliceMut`] for [`heapless::Vec`](https://docs.rs/heapless/) so that
+//! fixed-capacity vectors can be filled without a global allocator.
+//!
+//! ## `uuid`
+//!
+//! *(Off by default)*
+//!
+//! Provides `SecretTree::derive_uuid()` to derive deterministic, v4-shaped
+//! [`Uuid`](https://docs.rs/uuid/)s, e.g., as stable resource identifiers.
+//!
+//! ## `simd`
+//!
+//! *(Off by default)*
+//!
+//! Uses the [`blake2b_simd`](https://docs.rs/blake2b_simd/) crate as the Blake2b backend
+//! for derivation, which is accelerated with SIMD instructions (e.g., AVX2) where available.
+//! Derived secrets are identical regardless of the backend; only the speed changes.
+//! Instruction sets are detected at runtime if the `std` feature is enabled; otherwise,
+//! only instructions enabled at compile time (e.g., via `-C target-cpu`) are used.
+//! As with the default backend, the Blake2b state and outputs are zeroized after derivation;
+//! however, temporary state copies made internally by `blake2b_simd` are not.
+//!
+//! ## `aead`
+//!
+//! *(Off by default)*
+//!
+//! Provides `SecretTree::into_cipher_material()` to derive key / nonce pairs for AEAD ciphers
+//! implementing traits from the [`aead`](https://docs.rs/aead/) crate. **Read the method docs
+//! before using it;** deterministic nonces are only secure in specific use cases.
+//!
+//! ## `password`
+//!
+//! *(Off by default)*
+//!
+//! Provides `SecretTree::from_password()` to create trees from passwords using
+//! the [Argon2id](https://docs.rs/argon2/) password hashing function.
+//!
+//! ## `slip10`
+//!
+//! *(Off by default)*
+//!
+//! Provides the `slip10` module with [SLIP-0010] key derivation for the ed25519 curve,
+//! which allows interoperating with hardware wallets. This derivation is unrelated
+//! to the native derivation performed by `SecretTree`.
+//!
+//! [SLIP-0010]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+//!
+//! ## `testing`
+//!
+//! *(Off by default)*
+//!
+//! Provides [`SecretTree::from_test_seed()`] to create trees with well-known seeds derived
+//! from string labels. Such trees are useful as reproducible test fixtures. **Never** enable
+//! this feature in production code; the produced trees are insecure by design.
+//!
 //! # Implementation details
 //!
 //! `SecretTree` uses the [Blake2b] keyed hash function to derive the following kinds of data:
 //!
 //! - secret key
-//! - CSPRNG seed (the RNG used is [`ChaChaRng`])
+//! - CSPRNG seed (the RNG used is [`ChaChaRng`](https://docs.rs/rand_chacha/0.3/rand_chacha/struct.ChaChaRng.html))
 //! - seeds for child `SecretTree`s
 //!
 //! The procedure is similar to the use of Blake2b for key derivation in [libsodium]\:
@@ -36,18 +166,45 @@
 //! - The key is the seed of the `SecretTree` instance used for derivation.
 //! - The message is an empty bit string.
 //!
-//! The length of derived data is 32 bytes in all cases.
+//! The length of derived data is 32 bytes in all cases, except for [`TreeId`]s,
+//! which are 16 bytes, and secret keys, for which it is the byte length of the filled buffer.
+//!
+//! The only exception to the key being a `SecretTree` seed is [stretching](SecretTree::from_short_seed())
+//! of a short seed, in which case the short seed itself (1..=32 bytes) is used as the key.
 //!
 //! ## Salt and personalization
 //!
 //! | Data type | Salt | Personalization |
 //! |:----------|:-----|:----------------|
 //! | Secret key | `[0; 16]` | `b"bytes\0\0...\0"` |
-//! | CSPRNG seed | `[0; 16]` | `b"rng\0\0...\0"` |
-//! | Seed for a [named child](SecretTree::child()) | `name.as_bytes()` (zero-padded) | `b"name\0\0...\0"` |
+//! | [Large secret key](SecretTree::fill_xof()) (block `i`) | `LittleEndian(i)` | `b"xof\0\0...\0"` |
+//! | [Small secret key](SecretTree::try_fill_small()) (1..=15 bytes) | `[0; 16]` | `b"small\0\0\0"` |
+//! | CSPRNG seed | `LittleEndian(label)` (zero for `SecretTree::rng()`) | `b"rng\0\0...\0"` |
+//! | Seed for a [named child](SecretTree::child()) | [`name.as_bytes()`](Name::as_bytes()) (zero-padded) | `b"name\0\0...\0"` |
+//! | Seed for a [binary-identified child](SecretTree::child_raw()) | `bytes` zero-padded to 15 bytes, followed by `bytes.len()` | `b"raw\0\0...\0"` |
 //! | Seed for an [indexed child](SecretTree::index()) | `LittleEndian(index)` | `b"index\0\0...\0"` |
+//! | Seed for a [2D-indexed child](SecretTree::index2()) | `LittleEndian(a) \|\| LittleEndian(b)` | `b"index2\0\0"` |
 //! | Seed for a [digest child](SecretTree::digest()) (1st iter) | `digest[..16]` | `b"digest0\0\0...\0"` |
 //! | Seed for a digest child (2nd iter) | `digest[16..]` | `b"digest1\0\0...\0"` |
+//! | Seed [stretched](SecretTree::from_short_seed()) from a short seed | `[0; 16]` | `b"stretch\0"` |
+//! | Seed for a [child bound to associated data](SecretTree::bind()) | Blake2b-128 hash of `aad` | `b"bind\0\0\0\0"` |
+//! | Seed for a [split child](SecretTree::split()) | `LittleEndian(0)` (key tree) or `LittleEndian(1)` (RNG tree) | `b"split\0\0\0"` |
+//! | Seed for a [rotated tree](SecretTree::rotate()) (generation `>= 1`) | `LittleEndian(generation)` | `b"rotate\0\0"` |
+//! | AEAD key (`SecretTree::into_cipher_material()`) | `[0; 16]` | `b"aeadkey\0"` |
+//! | AEAD nonce (`SecretTree::into_cipher_material()`) | `[0; 16]` | `b"aeadnonc"` |
+//! | [Info-parameterized key](SecretTree::try_derive_info()) | Blake2b-128 hash of `info` | `b"info\0\0\0\0"` |
+//! | [Tree ID](SecretTree::id()) | `[0; 16]` | `b"tree-id\0"` |
+//! | SLIP-0010 seed (`SecretTree::slip10_master()`) | `[0; 16]` | `b"slip10\0\0"` |
+//! | Seed of a tree [narrowed](SecretTree64::into_tree()) from a 64-byte seed | `[0; 16]` | `b"narrow\0\0"` |
+//!
+//! All personalizations are exposed as constants in the [`contexts`] module.
+//!
+//! [`SecretTree64`] uses the same salts and personalizations, but with a 64-byte seed as the key
+//! and 64-byte outputs for child seeds.
+//!
+//! [Test seeds](SecretTree::from_test_seed()) (available with the `testing` feature) are
+//! derived from the all-zero 32-byte key, with the Blake2b-128 hash of the label as salt
+//! and `b"testseed"` personalization.
 //!
 //! Derivation of a secret key, CSPRNG seed and seeds for indexed children are
 //! all fully compatible with libsodium.
@@ -97,11 +254,12 @@
 //!   `rng()` and `fill()` methods consume the tree instance, which makes it harder to reuse
 //!   the same RNG for multiple purposes (which is not intended).
 //!
+//! [`ConstantTimeEq`]: https://docs.rs/subtle/2/subtle/trait.ConstantTimeEq.html
 //! [libsodium]: https://download.libsodium.org/doc/key_derivation
 //! [Blake2b]: https://tools.ietf.org/html/rfc7693
 //! [Pedersen commitments]: https://en.wikipedia.org/wiki/Commitment_scheme
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 // Documentation settings
 #![doc(html_root_url = "https://docs.rs/secret-tree/0.5.0")]
 // Linter settings
@@ -113,26 +271,84 @@
     clippy::module_name_repetitions
 )]
 
-#[cfg(all(not(feature = "std"), test))]
-extern crate std;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "rng")]
 use rand_chacha::ChaChaRng;
-use rand_core::{CryptoRng, RngCore, SeedableRng};
-use secrecy::{zeroize::Zeroize, CloneableSecret, ExposeSecret, SecretBox};
+use rand_core::{CryptoRng, CryptoRngCore, RngCore, SeedableRng};
+use secrecy::{
+    zeroize::{Zeroize, Zeroizing},
+    CloneableSecret, ExposeSecret, ExposeSecretMut, SecretBox,
+};
 
 use core::{
     array::TryFromSliceError,
     convert::TryInto,
     fmt,
+    hash::{Hash, Hasher},
+    mem,
+    ops::Range,
     str::{self, FromStr},
 };
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod bounded;
 mod byte_slice;
+pub mod contexts;
+mod domain;
+mod id;
 mod kdf;
+#[cfg(feature = "std")]
+mod memo;
+mod multi;
+mod path;
+#[cfg(feature = "rand09")]
+mod rand09;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "alloc")]
+mod set;
+#[cfg(feature = "slip10")]
+pub mod slip10;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "testing")]
+mod testing;
+mod wide;
 
-pub use crate::{byte_slice::AsByteSliceMut, kdf::SEED_LEN};
+pub use crate::{
+    bounded::{BoundedTree, CapacityError},
+    byte_slice::{AsByteSliceMut, FillTuple},
+    domain::DerivationDomain,
+    id::TreeId,
+    kdf::{derivation_block_count, CONTEXT_LEN, SEED_LEN},
+    multi::MultiDeriver,
+    path::{PathError, RawId, RecordedSegment, Segment, SplitHalf},
+    wide::{SecretTree64, Seed64, WIDE_SEED_LEN},
+};
+
+/// Re-export of the [`secrecy`](https://docs.rs/secrecy/) crate, whose types (e.g., [`SecretBox`])
+/// are used in the public API of this crate. Using the re-export guarantees that the `secrecy`
+/// version matches the one used by this crate, and removes the need to depend on `secrecy`
+/// directly just to call [`ExposeSecret::expose_secret()`].
+pub use secrecy;
+
+#[cfg(feature = "rand")]
+pub use crate::byte_slice::FillAdapter;
+#[cfg(feature = "std")]
+pub use crate::{memo::MemoizedTree, stream::DigestWriter};
+#[cfg(feature = "alloc")]
+pub use crate::{path::DerivationScript, set::SecretTreeSet};
 
-use crate::kdf::{derive_key, try_derive_key, Index, CONTEXT_LEN, SALT_LEN};
+use crate::{
+    kdf::{
+        derive_key, hash_to_salt, try_derive_key, try_derive_short_key, Index, MAX_KEY_LEN,
+        SALT_LEN,
+    },
+    path::parse_path,
+};
 
 /// Maximum byte length of a [`Name`] (16).
 pub const MAX_NAME_LEN: usize = SALT_LEN;
@@ -156,9 +372,13 @@ pub struct Seed(SecretBox<SeedBytes>);
 impl Seed {
     /// Generates a random seed using the provided RNG.
     pub fn new<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-        Self(SecretBox::<SeedBytes>::init_with_mut(|seed| {
-            rng.fill_bytes(&mut seed.0);
-        }))
+        Self::from_dyn_rng(rng)
+    }
+
+    /// Generates a random seed using the provided RNG trait object. Unlike [`Self::new()`],
+    /// this method is not generic, which is useful if the RNG is chosen at runtime.
+    pub fn from_dyn_rng(rng: &mut dyn CryptoRngCore) -> Self {
+        Self::init_with(|seed_bytes| rng.fill_bytes(seed_bytes))
     }
 
     fn init_with(init_fn: impl FnOnce(&mut [u8; SEED_LEN])) -> Self {
@@ -167,12 +387,72 @@ impl Seed {
         }))
     }
 
+    /// Checks whether this seed consists of zero bytes only. The check is constant-time.
+    fn is_all_zeros(&self) -> bool {
+        ct_eq(self.expose_secret(), &[0; SEED_LEN])
+    }
+
     /// Exposes the bytes contained in this seed.
     pub fn expose_secret(&self) -> &[u8; SEED_LEN] {
         &self.0.expose_secret().0
     }
 }
 
+/// Zeroizes the seed bytes in place. This is performed automatically when the seed is dropped.
+impl Zeroize for Seed {
+    fn zeroize(&mut self) {
+        self.0.expose_secret_mut().zeroize();
+    }
+}
+
+/// [`Seed`] wrapper that can be compared in constant time via [`ConstantTimeEq`], without
+/// exposing seed bytes to the calling code. As with `Seed`, the bytes are zeroized on drop.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{CtSeed, Name, SecretTree};
+/// # use rand::thread_rng;
+/// use subtle::ConstantTimeEq;
+///
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let seed = CtSeed::new(tree.child(Name::new("test")).seed().clone());
+/// let other_seed = CtSeed::from(tree.child(Name::new("test")).seed().clone());
+/// assert!(bool::from(seed.ct_eq(&other_seed)));
+/// ```
+///
+/// [`ConstantTimeEq`]: https://docs.rs/subtle/2/subtle/trait.ConstantTimeEq.html
+#[cfg(feature = "subtle")]
+#[derive(Debug, Clone)]
+pub struct CtSeed(Seed);
+
+#[cfg(feature = "subtle")]
+impl CtSeed {
+    /// Wraps the provided seed.
+    pub fn new(seed: Seed) -> Self {
+        Self(seed)
+    }
+
+    /// Unwraps the seed.
+    pub fn into_inner(self) -> Seed {
+        self.0
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl From<Seed> for CtSeed {
+    fn from(seed: Seed) -> Self {
+        Self(seed)
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for CtSeed {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.0.expose_secret().ct_eq(other.0.expose_secret())
+    }
+}
+
 /// Creates a seed from a (potentially unsecured) byte slice.
 impl From<&[u8; SEED_LEN]> for Seed {
     fn from(bytes: &[u8; SEED_LEN]) -> Self {
@@ -202,9 +482,8 @@ impl From<&[u8; SEED_LEN]> for Seed {
 /// # Examples
 ///
 /// ```
-/// use secret_tree::{SecretTree, Name};
+/// use secret_tree::{secrecy::{ExposeSecret, SecretBox}, SecretTree, Name};
 /// use rand::{Rng, thread_rng};
-/// use secrecy::{ExposeSecret, SecretBox};
 ///
 /// let tree = SecretTree::new(&mut thread_rng());
 /// // Don't forget to securely store secrets! Here, we wrap them
@@ -222,8 +501,7 @@ impl From<&[u8; SEED_LEN]> for Seed {
 ///     .collect();
 ///
 /// // The tree is compactly stored as a single 32-byte seed.
-/// let seed = tree.seed().clone();
-/// drop(tree);
+/// let seed = tree.into_seed();
 ///
 /// // If we restore the tree from the seed, we can restore all derived secrets.
 /// let tree = SecretTree::from_seed(seed);
@@ -239,26 +517,117 @@ impl From<&[u8; SEED_LEN]> for Seed {
 #[must_use = "A tree should generate a secret or child tree"]
 pub struct SecretTree {
     seed: Seed,
+    depth: usize,
+    /// Maximum allowed depth of children, counted from the root.
+    max_depth: Option<usize>,
+    #[cfg(feature = "track-path")]
+    path: alloc::vec::Vec<RecordedSegment>,
 }
 
 impl SecretTree {
-    const FILL_BYTES_CONTEXT: [u8; CONTEXT_LEN] = *b"bytes\0\0\0";
-    const RNG_CONTEXT: [u8; CONTEXT_LEN] = *b"rng\0\0\0\0\0";
-    const NAME_CONTEXT: [u8; CONTEXT_LEN] = *b"name\0\0\0\0";
-    const INDEX_CONTEXT: [u8; CONTEXT_LEN] = *b"index\0\0\0";
-    const DIGEST_START_CONTEXT: [u8; CONTEXT_LEN] = *b"digest0\0";
-    const DIGEST_END_CONTEXT: [u8; CONTEXT_LEN] = *b"digest1\0";
-
     /// Generates a tree by sampling its seed from the supplied RNG.
     pub fn new<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-        Self {
-            seed: Seed::new(rng),
-        }
+        Self::from_dyn_rng(rng)
+    }
+
+    /// Generates a tree by sampling its seed from the supplied RNG trait object.
+    /// Unlike [`Self::new()`], this method is not generic, which is useful if the RNG
+    /// is chosen at runtime (e.g., in plugin architectures).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rand::thread_rng;
+    /// # use rand_core::CryptoRngCore;
+    /// # use secret_tree::SecretTree;
+    /// let mut rng: Box<dyn CryptoRngCore> = Box::new(thread_rng());
+    /// let tree = SecretTree::from_dyn_rng(&mut *rng);
+    /// ```
+    pub fn from_dyn_rng(rng: &mut dyn CryptoRngCore) -> Self {
+        Self::from_seed(Seed::from_dyn_rng(rng))
     }
 
     /// Creates a tree from the seed.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the seed consists of zero bytes only, since this is
+    /// almost always a programming error (e.g., an uninitialized buffer).
+    /// Use [`Self::from_seed_checked()`] to check for this case in all builds.
+    #[track_caller]
     pub fn from_seed(seed: Seed) -> Self {
-        Self { seed }
+        debug_assert!(
+            !seed.is_all_zeros(),
+            "all-zero seed supplied to `SecretTree`; this is likely a misuse"
+        );
+        Self::from_seed_unchecked(seed)
+    }
+
+    /// Creates a tree from a reference to the seed. The seed bytes are copied into a fresh
+    /// heap allocation, so the caller retains ownership of the seed; this is useful
+    /// if a long-lived object (e.g., app config) holds the canonical seed.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Self::from_seed()`].
+    #[track_caller]
+    pub fn from_seed_ref(seed: &Seed) -> Self {
+        Self::from_seed(seed.clone())
+    }
+
+    /// Creates a tree from the seed, checking that the seed is not obviously weak.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeedError::WeakSeed`] if the seed consists of zero bytes only.
+    pub fn from_seed_checked(seed: Seed) -> Result<Self, SeedError> {
+        if seed.is_all_zeros() {
+            Err(SeedError::WeakSeed)
+        } else {
+            Ok(Self::from_seed_unchecked(seed))
+        }
+    }
+
+    fn from_seed_unchecked(seed: Seed) -> Self {
+        Self {
+            seed,
+            depth: 0,
+            max_depth: None,
+            #[cfg(feature = "track-path")]
+            path: alloc::vec::Vec::new(),
+        }
+    }
+
+    /// Copies this tree. Not exposed publicly to discourage duplicating secrets.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self {
+            seed: self.seed.clone(),
+            depth: self.depth,
+            max_depth: self.max_depth,
+            #[cfg(feature = "track-path")]
+            path: self.path.clone(),
+        }
+    }
+
+    fn derive_child(&self, segment: Segment, seed: Seed) -> Self {
+        self.derive_recorded_child(RecordedSegment::Segment(segment), seed)
+    }
+
+    fn derive_recorded_child(&self, segment: RecordedSegment, seed: Seed) -> Self {
+        #[cfg(not(feature = "track-path"))]
+        let _ = segment;
+
+        Self {
+            seed,
+            depth: self.depth + 1,
+            max_depth: self.max_depth,
+            #[cfg(feature = "track-path")]
+            path: {
+                let mut path = self.path.clone();
+                path.push(segment);
+                path
+            },
+        }
     }
 
     /// Restores a tree from the seed specified as a byte slice.
@@ -268,442 +637,3841 @@ impl SecretTree {
     /// Returns an error if `bytes` has an invalid length (not [`SEED_LEN`]).
     pub fn from_slice(bytes: &[u8]) -> Result<Self, TryFromSliceError> {
         let seed_ref: &[u8; 32] = bytes.try_into()?;
-        Ok(Self {
-            seed: seed_ref.into(),
-        })
+        Ok(Self::from_seed(seed_ref.into()))
     }
 
-    /// Returns the tree seed.
-    pub fn seed(&self) -> &Seed {
-        &self.seed
+    /// Restores a tree by reading exactly [`SEED_LEN`] raw seed bytes from `reader`.
+    /// The intermediate buffer is zeroized after use. This is the counterpart
+    /// of [`Self::to_writer()`].
+    ///
+    /// # Errors
+    ///
+    /// - Propagates IO errors from `reader`. In particular, returns an error
+    ///   with [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) kind if the reader
+    ///   has fewer than [`SEED_LEN`] bytes.
+    /// - Returns an error with [`InvalidData`](std::io::ErrorKind::InvalidData) kind
+    ///   if the read seed is [weak](SeedError::WeakSeed).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// # fn main() -> std::io::Result<()> {
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut buffer = vec![];
+    /// tree.to_writer(&mut buffer)?;
+    /// let restored_tree = SecretTree::from_reader(buffer.as_slice())?;
+    /// assert_eq!(restored_tree, tree);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut bytes = Zeroizing::new([0_u8; SEED_LEN]);
+        reader.read_exact(bytes.as_mut())?;
+        Self::from_seed_checked(Seed::from(&*bytes))
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
     }
 
-    /// Converts this tree into a cryptographically secure pseudo-random number generator
-    /// (CSPRNG). This RNG can then be used to reproducibly create secrets (e.g., secret keys).
+    /// Tries to create a tree from a password. The seed is produced with the Argon2id
+    /// password hashing function (version 0x13) with the following parameters:
+    ///
+    /// - Memory cost: 19,456 KiB (19 MiB)
+    /// - Time cost: 2 iterations
+    /// - Parallelism: 1 lane
+    /// - Output length: [`SEED_LEN`] bytes
+    ///
+    /// These parameters follow [OWASP recommendations] and are fixed, since changing them
+    /// would change all derived secrets. The Argon2 working memory is zeroized after use;
+    /// the output is written directly into the [`Seed`].
+    ///
+    /// `salt` should be unique per application (and ideally per user), but it does not need
+    /// to be secret.
     ///
     /// # Security
     ///
-    /// [`Self::fill()`] should be preferred if the secret allows it. While using a CSPRNG
-    /// to generate secrets is theoretically sound, it introduces a new entity that
-    /// may leak information.
-    /// `fill()` is especially useful if the filled buffer implements zeroing on drop;
-    /// the state of a CSPRNG generator returned by `rng()` **is not** zeroed on drop and thus
-    /// creates a potential attack vector. (However theoretical it may be; `ChaChaRng`
-    /// has a notably small state size - ~160 bytes, so it may be better localized
-    /// and have lower risk to be accessed by the adversary than other CSPRNG implementations.)
-    pub fn rng(self) -> ChaChaRng {
-        let mut seed = <ChaChaRng as SeedableRng>::Seed::default();
-        derive_key(
-            seed.as_mut(),
-            Index::None,
-            Self::RNG_CONTEXT,
-            self.seed.expose_secret(),
-        );
-        ChaChaRng::from_seed(seed)
-    }
-
-    /// Tries to fill the specified buffer with a key derived from the seed of this tree.
+    /// The entropy of the tree created this way is bounded by the entropy of the password.
+    /// Anyone learning a non-secret value derived from the tree (e.g., a public key
+    /// or a [tree ID](Self::id())) together with the salt can mount an offline brute-force attack
+    /// on the password, with Argon2id only slowing it down. In contrast, a tree created
+    /// from a random seed (e.g., with [`Self::new()`]) has full 256-bit entropy; if such a seed
+    /// is stored encrypted with a password, an attacker additionally needs the encrypted seed
+    /// to start guessing. Prefer random seeds unless re-deriving the whole hierarchy
+    /// from a memorized password is a hard requirement.
+    ///
+    /// [OWASP recommendations]: https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html#argon2id
     ///
     /// # Errors
     ///
-    /// Errors if the buffer does not have length `16..=64` bytes. Use [`Self::rng()`]
-    /// if the buffer size may be outside these bounds, or if the secret must be derived
-    /// in a more complex way.
-    pub fn try_fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) -> Result<(), FillError> {
-        try_derive_key(
-            dest.as_byte_slice_mut(),
-            Index::None,
-            Self::FILL_BYTES_CONTEXT,
-            self.seed.expose_secret(),
-        )?;
-        dest.convert_to_le();
-        Ok(())
+    /// Returns an error if `salt` has an invalid length (not in `8..=64` bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// let tree = SecretTree::try_from_password(b"correct horse battery staple", b"my-cli-tool")?;
+    /// # Ok::<_, secret_tree::LenError>(())
+    /// ```
+    #[cfg(feature = "password")]
+    pub fn try_from_password(password: &[u8], salt: &[u8]) -> Result<Self, LenError> {
+        const MIN_SALT_LEN: usize = 8;
+        const MAX_SALT_LEN: usize = 64;
+
+        if !(MIN_SALT_LEN..=MAX_SALT_LEN).contains(&salt.len()) {
+            return Err(LenError {
+                actual: salt.len(),
+                min: MIN_SALT_LEN,
+                max: MAX_SALT_LEN,
+            });
+        }
+        Ok(Self::from_seed_unchecked(Self::hash_password(
+            password, salt,
+        )))
     }
 
-    /// Fills the specified buffer with a key derived from the seed of this tree.
+    #[cfg(feature = "password")]
+    fn hash_password(password: &[u8], salt: &[u8]) -> Seed {
+        const M_COST: u32 = 19 * 1_024;
+        const T_COST: u32 = 2;
+        const P_COST: u32 = 1;
+
+        let params = argon2::Params::new(M_COST, T_COST, P_COST, Some(SEED_LEN))
+            .expect("Argon2 params are valid");
+        let argon2 =
+            argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+        Seed::init_with(|seed| {
+            argon2
+                .hash_password_into(password, salt, seed)
+                .expect("salt length is checked before");
+        })
+    }
+
+    /// Creates a tree from a password. See [`Self::try_from_password()`] for details
+    /// and security considerations.
     ///
     /// # Panics
     ///
-    /// Panics in the same cases when [`Self::try_fill()`] returns an error.
-    pub fn fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) {
-        self.try_fill(dest).unwrap_or_else(|err| {
-            panic!("Failed filling a buffer from `SecretTree`: {err}");
-        });
+    /// Panics in the same cases when [`Self::try_from_password()`] returns an error.
+    #[cfg(feature = "password")]
+    #[track_caller]
+    pub fn from_password(password: &[u8], salt: &[u8]) -> Self {
+        match Self::try_from_password(password, salt) {
+            Ok(tree) => tree,
+            Err(err) => panic!("Failed creating `SecretTree` from password: {err}"),
+        }
     }
 
-    /// Tries to create a secret by instantiating a buffer and filling it with a key derived from
-    /// the seed of this tree. Essentially, this is a more high-level wrapper around
-    /// [`Self::try_fill()`].
+    /// Writes [`SEED_LEN`] raw seed bytes of this tree to `writer`. The tree can be restored
+    /// from these bytes using [`Self::from_reader()`]. The writer is not flushed.
+    ///
+    /// # Security
+    ///
+    /// The seed leaves the control of this crate; it is up to the caller to ensure
+    /// that the sink handles it securely (e.g., the written file has restricted access).
     ///
     /// # Errors
     ///
-    /// Returns an error if `T` does not have length `16..=64` bytes. Use [`Self::rng()`]
-    /// if the buffer size may be outside these bounds, or if the secret must be derived
-    /// in a more complex way.
-    pub fn try_create_secret<T>(self) -> Result<SecretBox<T>, FillError>
-    where
-        T: AsByteSliceMut + Default + Zeroize,
-    {
-        let mut result = Ok(());
-        let secret = SecretBox::init_with_mut(|secret_value| {
-            result = self.try_fill(secret_value);
-        });
-        result?;
-        Ok(secret)
+    /// Propagates IO errors from `writer`.
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(self.seed.expose_secret())
     }
 
-    /// Creates a secret by instantiating a buffer and filling it with a key derived from
-    /// the seed of this tree.
+    /// Creates a tree from a seed shorter than [`SEED_LEN`] bytes, e.g., a 16-byte master secret
+    /// provided by a legacy system.
     ///
-    /// # Panics
+    /// Unlike [`Self::from_slice()`], the supplied bytes are not used as the seed directly;
+    /// instead, they are stretched to [`SEED_LEN`] bytes with keyed Blake2b, using `bytes` as
+    /// the key and a dedicated personalization (see the [crate-level docs](crate) for details).
+    /// Consequently, a 32-byte input results in a different tree than `from_slice()`.
     ///
-    /// Panics in the same cases when [`Self::try_create_secret()`] returns an error.
-    pub fn create_secret<T>(self) -> SecretBox<T>
-    where
-        T: AsByteSliceMut + Default + Zeroize,
-    {
-        self.try_create_secret().unwrap_or_else(|err| {
-            panic!("Failed creating a secret from `SecretTree`: {err}");
-        })
+    /// Stretching is deterministic, but it cannot add entropy; the resulting tree is only
+    /// as secure as the supplied short seed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is empty or longer than [`SEED_LEN`] bytes.
+    pub fn from_short_seed(bytes: &[u8]) -> Result<Self, LenError> {
+        const _: () = assert!(SEED_LEN <= MAX_KEY_LEN);
+
+        if bytes.is_empty() || bytes.len() > SEED_LEN {
+            return Err(LenError {
+                actual: bytes.len(),
+                min: 1,
+                max: SEED_LEN,
+            });
+        }
+        Ok(Self::from_seed(Seed::init_with(|seed| {
+            derive_key(seed, Index::None, contexts::STRETCH, bytes);
+        })))
     }
 
-    /// Produces a child with the specified string identifier.
-    pub fn child(&self, name: Name) -> Self {
-        Self::from_seed(Seed::init_with(|child_seed| {
-            derive_key(
-                child_seed,
-                Index::Bytes(name.0),
-                Self::NAME_CONTEXT,
-                self.seed.expose_secret(),
-            );
-        }))
+    /// Creates a placeholder tree with an all-zero seed. The placeholder can be used as a sentinel
+    /// value, e.g., to move a tree out of a mutable reference with [`mem::replace()`].
+    ///
+    /// **The placeholder must never be used for derivation.** In debug builds, deriving secrets
+    /// or children from a placeholder panics. `SecretTree` intentionally does not implement
+    /// [`Default`] to prevent silently using a placeholder in place of a real tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// struct Wallet {
+    ///     tree: SecretTree,
+    /// }
+    ///
+    /// let mut wallet = Wallet {
+    ///     tree: SecretTree::new(&mut thread_rng()),
+    /// };
+    /// let tree = std::mem::replace(&mut wallet.tree, SecretTree::placeholder());
+    /// let rng = tree.rng();
+    /// ```
+    pub fn placeholder() -> Self {
+        Self::from_seed_unchecked(Seed::from(&[0; SEED_LEN]))
     }
 
-    /// Produces a child with the specified integer index.
-    pub fn index(&self, index: u64) -> Self {
-        Self::from_seed(Seed::init_with(|child_seed| {
-            derive_key(
-                child_seed,
-                Index::Number(index),
-                Self::INDEX_CONTEXT,
-                self.seed.expose_secret(),
-            );
-        }))
+    /// Returns the seed for use in derivation, checking in debug builds that the tree
+    /// is not a [placeholder](Self::placeholder()).
+    #[track_caller]
+    fn derivation_seed(&self) -> &[u8; SEED_LEN] {
+        debug_assert!(
+            !self.seed.is_all_zeros(),
+            "attempted derivation from a placeholder `SecretTree` with an all-zero seed"
+        );
+        self.seed.expose_secret()
     }
 
-    /// Produces a child with the specified 32-byte digest (e.g., an output of SHA-256,
-    /// SHA3-256 or Keccak256 hash functions).
-    ///
-    /// This method can be used for arbitrarily-sized keys by first digesting them
-    /// with a collision-resistant hash function.
-    pub fn digest(&self, digest: &[u8; 32]) -> Self {
-        let mut first_half_of_digest = [0_u8; SALT_LEN];
-        first_half_of_digest.copy_from_slice(&digest[0..SALT_LEN]);
-        let mut second_half_of_digest = [0_u8; SALT_LEN];
-        second_half_of_digest.copy_from_slice(&digest[SALT_LEN..]);
-
-        let intermediate_seed = Seed::init_with(|intermediate_seed| {
-            derive_key(
-                intermediate_seed,
-                Index::Bytes(first_half_of_digest),
-                Self::DIGEST_START_CONTEXT,
-                self.seed.expose_secret(),
-            );
-        });
-
-        Self::from_seed(Seed::init_with(|child_seed| {
-            derive_key(
-                child_seed,
-                Index::Bytes(second_half_of_digest),
-                Self::DIGEST_END_CONTEXT,
-                intermediate_seed.expose_secret(),
-            );
-        }))
+    /// Derives the seed of the [indexed child](Self::index()) with the specified index.
+    #[track_caller]
+    fn derive_index_seed(&self, index: u64, child_seed: &mut [u8; SEED_LEN]) {
+        derive_key(
+            child_seed,
+            Index::Number(index),
+            contexts::INDEX,
+            self.derivation_seed(),
+        );
     }
-}
 
-/// Errors that can occur when calling [`SecretTree::try_fill()`].
-#[derive(Debug)]
-#[non_exhaustive]
-pub enum FillError {
-    /// The supplied buffer is too small to be filled.
-    BufferTooSmall {
-        /// Byte size of the supplied buffer.
-        size: usize,
-        /// Minimum byte size for supported buffers.
-        min_supported_size: usize,
-    },
-    /// The supplied buffer is too large to be filled.
-    BufferTooLarge {
-        /// Byte size of the supplied buffer.
-        size: usize,
-        /// Maximum byte size for supported buffers.
-        max_supported_size: usize,
-    },
-}
+    /// Returns the tree seed.
+    pub fn seed(&self) -> &Seed {
+        &self.seed
+    }
 
-impl fmt::Display for FillError {
-    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::BufferTooSmall {
-                size,
-                min_supported_size,
-            } => {
-                write!(
-                    formatter,
-                    "supplied buffer ({size} bytes) is too small to be filled; \
-                     min supported size is {min_supported_size} bytes"
-                )
-            }
+    /// Returns an owned copy of the tree seed. This is equivalent to `self.seed().clone()`.
+    pub fn clone_seed(&self) -> Seed {
+        self.seed.clone()
+    }
 
-            Self::BufferTooLarge {
-                size,
-                max_supported_size,
-            } => {
-                write!(
-                    formatter,
-                    "supplied buffer ({size} bytes) is too large to be filled; \
-                     max supported size is {max_supported_size} bytes"
-                )
-            }
-        }
+    /// Consumes this tree and returns its seed. This is the inverse of [`Self::from_seed()`].
+    /// Derivation metadata (e.g., the [depth](Self::depth())) is discarded.
+    pub fn into_seed(self) -> Seed {
+        self.seed
     }
-}
 
-#[cfg(feature = "std")]
-impl std::error::Error for FillError {}
+    /// Returns an owned copy of the tree seed, which is zeroized on drop. This is safer
+    /// than copying the array obtained via [`Self::seed()`], which is not zeroized.
+    pub fn seed_bytes(&self) -> Zeroizing<[u8; SEED_LEN]> {
+        Zeroizing::new(*self.seed.expose_secret())
+    }
 
-/// Name of a child [`SecretTree`].
-///
-/// Used in [`SecretTree::child()`]; see its documentation for more context.
-///
-/// An original `str` can be extracted from `Name` using [`AsRef`] / [`Display`](fmt::Display)
-/// implementations:
-///
-/// ```
-/// # use secret_tree::Name;
-/// const NAME: Name = Name::new("test_name");
-/// assert_eq!(NAME.as_ref(), "test_name");
-/// assert_eq!(NAME.to_string(), "test_name");
-/// ```
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Name([u8; SALT_LEN]);
+    /// Computes a non-secret identifier of this tree. See [`TreeId`] docs for details.
+    pub fn id(&self) -> TreeId {
+        let mut id_bytes = [0_u8; 16];
+        // The ID is not a derived secret, so it is fine to compute it for a placeholder tree
+        // (e.g., when formatting or hashing it).
+        derive_key(
+            &mut id_bytes,
+            Index::None,
+            contexts::TREE_ID,
+            self.seed.expose_secret(),
+        );
+        TreeId::from_bytes(id_bytes)
+    }
 
-impl Name {
-    /// Creates a new `Name`.
+    /// Checks whether the [identifier](Self::id()) of this tree matches the expected one,
+    /// e.g., one published when the tree was set up. This can be used to detect mistakes
+    /// when reconstructing a tree (e.g., using a wrong derivation path) before using
+    /// secrets derived from it.
     ///
-    /// The supplied string must be no more than [`MAX_NAME_LEN`] bytes in length
-    /// and must not contain null chars `'\0'`.
+    /// The comparison is constant-time.
     ///
-    /// This is a constant method, which perform all relevant checks during compilation in
-    /// a constant context:
+    /// # Examples
     ///
     /// ```
-    /// # use secret_tree::Name;
-    /// const NAME: Name = Name::new("some_name");
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let published_id = tree.child(Name::new("signing")).id();
+    /// // Later...
+    /// assert!(tree.child(Name::new("signing")).verify_id(&published_id));
+    /// assert!(!tree.child(Name::new("encryption")).verify_id(&published_id));
     /// ```
+    pub fn verify_id(&self, expected: &TreeId) -> bool {
+        ct_eq(self.id().as_bytes(), expected.as_bytes())
+    }
+
+    /// Heuristically checks whether the seed of this tree looks like a placeholder
+    /// or a test seed rather than a random one. This is meant to catch developer mistakes,
+    /// e.g., a test seed accidentally shipped to production; it may be called on startup
+    /// in debug builds.
     ///
-    /// For example, this won't compile since the name is too long (17 chars):
+    /// The seed is flagged if it:
     ///
-    /// ```compile_fail
-    /// # use secret_tree::Name;
-    /// const OVERLY_LONG_NAME: Name = Name::new("Overly long name!");
-    /// ```
+    /// - consists of a short (up to 4 bytes) repeated pattern, e.g., all zeros or `[1, 2, 1, 2, ..]`
+    /// - forms an arithmetic progression modulo 256, e.g., `[0, 1, 2, ..]` or `[31, 30, 29, ..]`
+    /// - consists of printable ASCII chars only, e.g., a passphrase
     ///
-    /// ...And this won't compile because the name contains a `\0` char:
+    /// A random seed is flagged with a negligible probability (less than 2<sup>-40</sup>).
     ///
-    /// ```compile_fail
-    /// # use secret_tree::Name;
-    /// const NAME_WITH_ZERO_CHARS: Name = Name::new("12\03");
+    /// **This is a heuristic, not a security guarantee.** A `false` return value does not mean
+    /// that the seed has sufficient entropy; e.g., a hash of a guessable password passes the check.
+    /// The check is not constant-time, so it should not be performed on hot paths
+    /// observable by an adversary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// assert!(!tree.seed_looks_weak());
+    /// let sequential_seed: [u8; 32] = core::array::from_fn(|i| i as u8);
+    /// let tree = SecretTree::from_slice(&sequential_seed)?;
+    /// assert!(tree.seed_looks_weak());
+    /// # Ok::<_, core::array::TryFromSliceError>(())
     /// ```
+    pub fn seed_looks_weak(&self) -> bool {
+        const MAX_PERIOD: usize = 4;
+
+        let seed = self.seed.expose_secret();
+        let is_periodic =
+            (1..=MAX_PERIOD).any(|period| seed.iter().zip(&seed[period..]).all(|(x, y)| x == y));
+        let step = seed[1].wrapping_sub(seed[0]);
+        let is_progression = seed
+            .windows(2)
+            .all(|pair| pair[1].wrapping_sub(pair[0]) == step);
+        let is_ascii = seed
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ');
+        is_periodic || is_progression || is_ascii
+    }
+
+    /// Checks whether the seed of this tree consists of zero bytes only. Used to check
+    /// that zeroization works as intended.
+    #[cfg(test)]
+    pub(crate) fn is_zeroed(&self) -> bool {
+        self.seed.expose_secret().iter().all(|&byte| byte == 0)
+    }
+
+    /// Returns the number of derivations separating this tree from the root, i.e., from a tree
+    /// created with [`Self::new()`], [`Self::from_seed()`] or [`Self::from_slice()`].
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Limits the depth of the hierarchy derived from this tree: children can be derived
+    /// at most `max_depth` levels below this tree. The limit is propagated to all derived
+    /// children. If the tree already has a limit (e.g., inherited from its parent),
+    /// the stricter of the two limits applies; i.e., this method can only tighten the limit.
     ///
-    /// # Panics
+    /// This guards against accidentally unbounded derivation, e.g., if the derivation path
+    /// is supplied by an untrusted party. Use [`Self::try_child()`], [`Self::try_index()`]
+    /// and [`Self::try_digest()`] to handle exceeding the limit gracefully; infallible derivation
+    /// methods panic in this case.
     ///
-    /// Panics if `name` is overly long or contains null chars.
-    /// Use the [`FromStr`] implementation for a fallible / non-panicking alternative.
-    pub const fn new(name: &str) -> Self {
-        let bytes = name.as_bytes();
-        assert!(
-            bytes.len() <= SALT_LEN,
-            "name is too long (should be <=16 bytes)"
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng()).with_max_depth(2);
+    /// let child = tree.try_child(Name::new("foo"))?.try_index(1)?;
+    /// assert!(child.try_index(2).is_err());
+    /// # Ok::<_, secret_tree::DepthError>(())
+    /// ```
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        let max_depth = self.depth.saturating_add(max_depth);
+        self.max_depth = Some(
+            self.max_depth
+                .map_or(max_depth, |limit| limit.min(max_depth)),
         );
+        self
+    }
 
-        let mut i = 0;
-        let mut buffer = [0_u8; SALT_LEN];
-        while i < name.len() {
-            assert!(bytes[i] != 0, "name contains a null char");
-            buffer[i] = bytes[i];
-            i += 1;
+    fn check_depth(&self) -> Result<(), DepthError> {
+        match self.max_depth {
+            Some(max_depth) if self.depth >= max_depth => Err(DepthError { max_depth }),
+            _ => Ok(()),
         }
-        Name(buffer)
     }
-}
-
-impl FromStr for Name {
-    type Err = NameError;
 
-    fn from_str(name: &str) -> Result<Self, Self::Err> {
-        let byte_len = name.as_bytes().len();
-        if byte_len > SALT_LEN {
-            return Err(NameError::TooLong);
+    #[track_caller]
+    fn assert_depth(&self) {
+        if let Err(err) = self.check_depth() {
+            panic!("Failed deriving a child from `SecretTree`: {err}");
         }
-        if name.as_bytes().contains(&0) {
-            return Err(NameError::NullChar);
+    }
+
+    /// Returns the derivation path of this tree relative to the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree, Segment};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let child = tree.child(Name::new("foo")).index(3);
+    /// assert_eq!(child.depth(), 2);
+    /// assert_eq!(
+    ///     child.path(),
+    ///     [Segment::Name(Name::new("foo")), Segment::Index(3)]
+    /// );
+    /// ```
+    #[cfg(feature = "track-path")]
+    pub fn path(&self) -> &[RecordedSegment] {
+        &self.path
+    }
+
+    /// Converts this tree into a cryptographically secure pseudo-random number generator
+    /// (CSPRNG). This RNG can then be used to reproducibly create secrets (e.g., secret keys).
+    ///
+    /// # Security
+    ///
+    /// [`Self::fill()`] should be preferred if the secret allows it. While using a CSPRNG
+    /// to generate secrets is theoretically sound, it introduces a new entity that
+    /// may leak information.
+    /// `fill()` is especially useful if the filled buffer implements zeroing on drop;
+    /// the state of a CSPRNG generator returned by `rng()` **is not** zeroed on drop and thus
+    /// creates a potential attack vector. (However theoretical it may be; `ChaChaRng`
+    /// has a notably small state size - ~160 bytes, so it may be better localized
+    /// and have lower risk to be accessed by the adversary than other CSPRNG implementations.)
+    #[cfg(feature = "rng")]
+    pub fn rng(self) -> ChaChaRng {
+        self.rng_labeled(0)
+    }
+
+    /// Same as [`Self::rng()`], but checks that the seed of this tree is not obviously weak.
+    /// A tree with an all-zero seed (e.g., a [placeholder](Self::placeholder()) or a tree
+    /// restored from an uninitialized buffer) would produce a fully predictable RNG stream.
+    ///
+    /// Unlike the debug assertion in [`Self::rng()`], the check is performed in release builds
+    /// as well. The check is constant-time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seed of this tree consists of zero bytes only.
+    #[cfg(feature = "rng")]
+    pub fn try_rng(self) -> Result<ChaChaRng, SeedError> {
+        if self.seed.is_all_zeros() {
+            return Err(SeedError::WeakSeed);
         }
+        Ok(self.rng())
+    }
 
-        let mut bytes = [0; SALT_LEN];
-        bytes[..byte_len].copy_from_slice(name.as_bytes());
-        Ok(Self(bytes))
+    /// Same as [`Self::rng()`], but returns the CSPRNG as a boxed trait object. This is useful
+    /// to store the RNG uniformly with other RNG sources, e.g., in plugin-style code.
+    /// Prefer [`Self::rng()`] in performance-sensitive code.
+    ///
+    /// # Security
+    ///
+    /// The same considerations as for [`Self::rng()`] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rand::{thread_rng, RngCore};
+    /// # use rand_core::CryptoRngCore;
+    /// # use secret_tree::SecretTree;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut rngs: Vec<Box<dyn CryptoRngCore>> = vec![Box::new(thread_rng())];
+    /// rngs.push(tree.rng_boxed());
+    /// for rng in &mut rngs {
+    ///     let _ = rng.next_u64();
+    /// }
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "rng"))]
+    pub fn rng_boxed(self) -> alloc::boxed::Box<dyn CryptoRngCore> {
+        alloc::boxed::Box::new(self.rng())
     }
-}
 
-impl AsRef<str> for Name {
-    fn as_ref(&self) -> &str {
-        let str_len = self.0.iter().position(|&ch| ch == 0).unwrap_or(SALT_LEN);
-        unsafe {
-            // SAFETY: safe by construction; we only ever create `Name`s from valid UTF-8 sequences.
-            str::from_utf8_unchecked(&self.0[..str_len])
+    /// Shuffles the provided slice in place using the CSPRNG derived from this tree
+    /// (i.e., the RNG returned by [`Self::rng()`]). The shuffling algorithm is provided
+    /// by [`SliceRandom::shuffle()`](https://docs.rs/rand/0.8/rand/seq/trait.SliceRandom.html#tymethod.shuffle)
+    /// from `rand` v0.8; thus, the produced permutation is deterministic for a fixed tree
+    /// and slice length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut items: Vec<_> = (0..10).collect();
+    /// tree.child(Name::new("shuffle")).shuffle(&mut items);
+    /// # let mut sorted_items = items.clone();
+    /// # sorted_items.sort_unstable();
+    /// # assert_eq!(sorted_items, (0..10).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn shuffle<T>(self, items: &mut [T]) {
+        use rand::seq::SliceRandom as _;
+
+        items.shuffle(&mut self.rng());
+    }
+
+    /// Tries to convert this tree into an RNG of the specified type. The RNG seed is derived
+    /// in the same way as for [`Self::rng()`]; thus, `rng_as::<ChaChaRng>()` is equivalent
+    /// to `rng()`. Unlike `rng()`, the RNG seed may have any length supported
+    /// by [`Self::try_fill()`].
+    ///
+    /// Seeding in `rand_core` is infallible, so the only source of errors is an unsupported
+    /// seed length. RNGs with fallible construction (e.g., ones wrapping hardware sources)
+    /// can be instantiated from a buffer filled with [`Self::try_fill()`] instead.
+    ///
+    /// # Security
+    ///
+    /// The same caveats as for [`Self::rng()`] apply. Additionally, the RNG type must be
+    /// cryptographically secure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seed of `R` does not have length `16..=64` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// use rand_chacha::ChaCha12Rng;
+    ///
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let rng: ChaCha12Rng = tree.try_rng_as()?;
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_rng_as<R: SeedableRng>(self) -> Result<R, FillError> {
+        let mut seed = R::Seed::default();
+        try_derive_key(
+            seed.as_mut(),
+            Index::None,
+            contexts::RNG,
+            self.derivation_seed(),
+        )?;
+        // `R::Seed` is not necessarily `Copy`, so we move the seed out, leaving the default
+        // value in its place, and zeroize the buffer afterwards in any case.
+        let rng = R::from_seed(mem::take(&mut seed));
+        seed.as_mut().zeroize();
+        Ok(rng)
+    }
+
+    /// Converts this tree into an RNG of the specified type.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_rng_as()`] returns an error.
+    #[track_caller]
+    pub fn rng_as<R: SeedableRng>(self) -> R {
+        match self.try_rng_as() {
+            Ok(rng) => rng,
+            Err(err) => panic!("Failed creating an RNG from `SecretTree`: {err}"),
         }
     }
-}
 
-impl fmt::Debug for Name {
-    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.debug_tuple("Name").field(&self.as_ref()).finish()
+    /// Converts this tree into a CSPRNG with the specified label. RNGs with different labels
+    /// derived from the same seed are independent. Like [`Self::rng()`], this method consumes
+    /// the tree.
+    ///
+    /// The label is placed into the salt section of the Blake2b initialization block
+    /// (see the [crate-level docs](crate) for details). The RNG with label 0 is equivalent
+    /// to one returned by [`Self::rng()`].
+    ///
+    /// # Security
+    ///
+    /// The same caveats as for [`Self::rng()`] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::{thread_rng, Rng};
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut first_rng = tree.index(0).rng_labeled(0);
+    /// let mut second_rng = tree.index(0).rng_labeled(1);
+    /// assert_ne!(first_rng.gen::<u128>(), second_rng.gen::<u128>());
+    /// ```
+    #[cfg(feature = "rng")]
+    pub fn rng_labeled(self, label: u64) -> ChaChaRng {
+        let mut seed = <ChaChaRng as SeedableRng>::Seed::default();
+        self.derive_rng_seed(label, &mut seed);
+        ChaChaRng::from_seed(seed)
+    }
+
+    #[cfg(any(feature = "rng", feature = "rand09"))]
+    fn derive_rng_seed(&self, label: u64, rng_seed: &mut [u8; SEED_LEN]) {
+        derive_key(
+            rng_seed,
+            Index::Number(label),
+            contexts::RNG,
+            self.derivation_seed(),
+        );
+    }
+
+    /// Writes `len` bytes produced by the CSPRNG derived from this tree into `writer`.
+    ///
+    /// The bytes are the same as produced by [`Self::rng()`] and are streamed in fixed-size chunks,
+    /// so the entire output is never held in memory. Unlike `rng()`, this method does not consume
+    /// the tree. The writer is not flushed; IO errors are propagated as is.
+    ///
+    /// # Security
+    ///
+    /// This method is backed by the CSPRNG, so the caveats listed for [`Self::rng()`] apply.
+    /// Additionally, the written bytes leave the control of this crate; it is up to the caller
+    /// to ensure that the sink handles them securely.
+    #[cfg(all(feature = "std", feature = "rng"))]
+    pub fn write_to<W: std::io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        len: usize,
+    ) -> std::io::Result<()> {
+        const CHUNK_LEN: usize = 64;
+
+        let mut rng_seed = <ChaChaRng as SeedableRng>::Seed::default();
+        self.derive_rng_seed(0, &mut rng_seed);
+        let mut rng = ChaChaRng::from_seed(rng_seed);
+        rng_seed.zeroize();
+        let mut chunk = [0_u8; CHUNK_LEN];
+        let mut remaining = len;
+        let result = loop {
+            if remaining == 0 {
+                break Ok(());
+            }
+            let chunk_len = remaining.min(CHUNK_LEN);
+            rng.fill_bytes(&mut chunk[..chunk_len]);
+            if let Err(err) = writer.write_all(&chunk[..chunk_len]) {
+                break Err(err);
+            }
+            remaining -= chunk_len;
+        };
+        chunk.zeroize();
+        result
+    }
+
+    /// Tries to fill the specified buffer with a key derived from the seed of this tree.
+    ///
+    /// # Buffer length
+    ///
+    /// The supported buffer length is measured in **bytes**, not in elements. Thus, the number
+    /// of elements that can be filled depends on the element type:
+    ///
+    /// | Element type | Element count |
+    /// |:-------------|:--------------|
+    /// | `u8`, `i8` | `16..=64` |
+    /// | `u16`, `i16` | `8..=32` |
+    /// | `u32`, `i32`, `f32` | `4..=16` |
+    /// | `u64`, `i64`, `f64` | `2..=8` |
+    /// | `u128`, `i128` | `1..=4` |
+    ///
+    /// For example, `[u32; 4]` is a valid 16-byte buffer, while `[u64; 10]` is an invalid
+    /// 80-byte one. For sized buffers, the byte length can be checked
+    /// with [`Self::fill_len_for()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the buffer does not have length `16..=64` bytes. Use [`Self::rng()`]
+    /// if the buffer size may be outside these bounds, or if the secret must be derived
+    /// in a more complex way.
+    pub fn try_fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) -> Result<(), FillError> {
+        Self::fill_from_seed(self.derivation_seed(), dest)
+    }
+
+    /// Same as [`Self::try_fill()`], but returns the number of bytes written to the buffer
+    /// on success. This is useful in generic code, in which the buffer size is not known
+    /// in advance.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same cases as [`Self::try_fill()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{AsByteSliceMut, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut key = [0_u64; 4];
+    /// let dest: &mut dyn AsByteSliceMut = &mut key;
+    /// let written = tree.try_fill_counted(dest)?;
+    /// assert_eq!(written, 32);
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_fill_counted<T: AsByteSliceMut + ?Sized>(
+        self,
+        dest: &mut T,
+    ) -> Result<usize, FillError> {
+        let byte_len = dest.as_byte_slice_mut().len();
+        self.try_fill(dest)?;
+        Ok(byte_len)
+    }
+
+    /// Returns the byte length of a buffer of type `T` as it is understood by [`Self::try_fill()`].
+    /// Buffers with the length outside `16..=64` cannot be filled.
+    ///
+    /// The length is determined by the type alone (it is equal to `mem::size_of::<T>()`),
+    /// so it is only meaningful for fixed-size buffers: numbers, arrays, and [`Zeroizing`]
+    /// wrappers around them. In particular, it is **not** the filled length for `heapless::Vec`
+    /// (with the `heapless` feature), which depends on the current vector length; use
+    /// [`Self::try_fill_counted()`] to get the filled length for such buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// const _: () = assert!(SecretTree::fill_len_for::<[u32; 4]>() == 16);
+    /// assert_eq!(SecretTree::fill_len_for::<[u64; 10]>(), 80);
+    /// ```
+    pub const fn fill_len_for<T: AsByteSliceMut>() -> usize {
+        mem::size_of::<T>()
+    }
+
+    fn fill_from_seed<T: AsByteSliceMut + ?Sized>(
+        seed: &[u8; SEED_LEN],
+        dest: &mut T,
+    ) -> Result<(), FillError> {
+        try_derive_key(
+            dest.as_byte_slice_mut(),
+            Index::None,
+            contexts::FILL_BYTES,
+            seed,
+        )?;
+        dest.convert_to_le();
+        Ok(())
+    }
+
+    /// Fills the specified buffer with a key derived from the seed of this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill()`] returns an error.
+    #[track_caller]
+    pub fn fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) {
+        if let Err(err) = self.try_fill(dest) {
+            panic!("Failed filling a buffer from `SecretTree`: {err}");
+        }
+    }
+
+    /// Fills the specified buffer of any size with bytes derived from this tree. Unlike
+    /// [`Self::fill()`], the buffer is not limited to `16..=64` bytes, and unlike filling
+    /// from the [CSPRNG](Self::rng()), this method does not depend on the `rng` feature.
+    ///
+    /// The output is produced by Blake2b in the counter mode, in 64-byte blocks: block `i` is
+    /// a 64-byte key derived from the seed of this tree with `LittleEndian(i)` as the salt
+    /// and a dedicated personalization (see the [crate-level docs](crate#salt-and-personalization)).
+    /// The last block is truncated if necessary. Consequently, outputs of different lengths
+    /// are prefixes of each other, and the output is independent of one produced
+    /// by `fill()` or `rng()` for the same tree. As with `fill()`, multi-byte values
+    /// are converted to the little-endian byte order after filling, so the output
+    /// is stable across platforms; it is also covered by stability tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut large_key = [0_u8; 256];
+    /// tree.index(0).fill_xof(&mut large_key);
+    ///
+    /// let mut prefix = [0_u8; 100];
+    /// tree.index(0).fill_xof(&mut prefix);
+    /// assert_eq!(prefix, large_key[..100]);
+    /// ```
+    pub fn fill_xof<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) {
+        let mut block = [0_u8; MAX_KEY_LEN];
+        let chunks = dest.as_byte_slice_mut().chunks_mut(MAX_KEY_LEN);
+        for (counter, chunk) in (0_u64..).zip(chunks) {
+            derive_key(
+                &mut block,
+                Index::Number(counter),
+                contexts::XOF,
+                self.derivation_seed(),
+            );
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        block.zeroize();
+        dest.convert_to_le();
+    }
+
+    /// Tries to XOR a key derived from the seed of this tree into the specified buffer.
+    /// The key is derived in the same way as with [`Self::try_fill()`]; the only difference
+    /// is that the buffer is combined with the key rather than overwritten. The XOR operation
+    /// is performed on the little-endian representation of buffer elements, so the result
+    /// does not depend on the platform endianness.
+    ///
+    /// This is useful to combine secret material from multiple sources, e.g., to mask
+    /// a secret with keys derived from several trees in the one-time-pad fashion.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same cases as [`Self::try_fill()`], i.e., if the buffer does not have
+    /// length `16..=64` bytes. The buffer is not modified in this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut secret = [42_u64; 4];
+    /// tree.index(0).try_xor_fill(&mut secret)?;
+    /// tree.index(0).try_xor_fill(&mut secret)?;
+    /// // XORing the same key twice restores the original value.
+    /// assert_eq!(secret, [42; 4]);
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_xor_fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) -> Result<(), FillError> {
+        let byte_len = dest.as_byte_slice_mut().len();
+        kdf::check_key_len(byte_len)?;
+        // The length check above ensures that the key fits into the buffer.
+        let mut key = Zeroizing::new([0_u8; kdf::MAX_OUTPUT_LEN]);
+        let key = &mut key[..byte_len];
+        derive_key(
+            key,
+            Index::None,
+            contexts::FILL_BYTES,
+            self.derivation_seed(),
+        );
+
+        dest.convert_to_le();
+        for (dest_byte, key_byte) in dest.as_byte_slice_mut().iter_mut().zip(&*key) {
+            *dest_byte ^= key_byte;
+        }
+        dest.convert_to_le();
+        Ok(())
+    }
+
+    /// XORs a key derived from the seed of this tree into the specified buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_xor_fill()`] returns an error.
+    #[track_caller]
+    pub fn xor_fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) {
+        if let Err(err) = self.try_xor_fill(dest) {
+            panic!("Failed filling a buffer from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to fill a small buffer (`1..=15` bytes) with a key derived from the seed of this tree.
+    /// This is useful for short secrets, such as 12-byte nonces or 8-byte tags.
+    ///
+    /// Blake2b natively supports such output lengths, but libsodium does not; thus, this method
+    /// is **not** libsodium-compatible. It uses a dedicated personalization (see
+    /// the [crate-level docs](crate) for details), so the output is unrelated to a truncated
+    /// output of [`Self::try_fill()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the buffer does not have length `1..=15` bytes. Use [`Self::try_fill()`]
+    /// for larger buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut nonce = [0_u8; 12];
+    /// tree.child(Name::new("nonce")).try_fill_small(&mut nonce)?;
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_fill_small<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) -> Result<(), FillError> {
+        try_derive_short_key(
+            dest.as_byte_slice_mut(),
+            Index::None,
+            contexts::FILL_SMALL_BYTES,
+            self.derivation_seed(),
+        )?;
+        dest.convert_to_le();
+        Ok(())
+    }
+
+    /// Fills a small buffer (`1..=15` bytes) with a key derived from the seed of this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill_small()`] returns an error.
+    #[track_caller]
+    pub fn fill_small<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) {
+        if let Err(err) = self.try_fill_small(dest) {
+            panic!("Failed filling a buffer from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to fill the specified buffer with a key derived from the seed of this tree,
+    /// and then post-processes the filled bytes with the provided closure. This is useful
+    /// for key types requiring a fixed transformation of the derived bytes (e.g., clamping
+    /// of Ed25519 / X25519 scalars) without copying these bytes to a temporary buffer.
+    ///
+    /// The closure is called on the derived bytes before they are converted to the native
+    /// representation of buffer elements (as with [`Self::try_fill()`]). Thus, the closure
+    /// always sees multi-byte elements laid out in the little-endian order, regardless
+    /// of the platform endianness, and its output does not depend on the platform either.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same cases as [`Self::try_fill()`]. In this case, the closure
+    /// is not called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// # use secrecy::SecretBox;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let scalar = SecretBox::<[u8; 32]>::init_with_mut(|bytes| {
+    ///     tree.fill_with(bytes, |bytes| {
+    ///         bytes[0] &= 248;
+    ///         bytes[31] &= 127;
+    ///         bytes[31] |= 64;
+    ///     });
+    /// });
+    /// # use secrecy::ExposeSecret;
+    /// # assert_eq!(scalar.expose_secret()[0] & 7, 0);
+    /// ```
+    pub fn try_fill_with<T, F>(self, dest: &mut T, post_process: F) -> Result<(), FillError>
+    where
+        T: AsByteSliceMut + ?Sized,
+        F: FnOnce(&mut [u8]),
+    {
+        try_derive_key(
+            dest.as_byte_slice_mut(),
+            Index::None,
+            contexts::FILL_BYTES,
+            self.derivation_seed(),
+        )?;
+        post_process(dest.as_byte_slice_mut());
+        dest.convert_to_le();
+        Ok(())
+    }
+
+    /// Fills the specified buffer with a key derived from the seed of this tree,
+    /// and then post-processes the filled bytes with the provided closure.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill_with()`] returns an error.
+    #[track_caller]
+    pub fn fill_with<T, F>(self, dest: &mut T, post_process: F)
+    where
+        T: AsByteSliceMut + ?Sized,
+        F: FnOnce(&mut [u8]),
+    {
+        if let Err(err) = self.try_fill_with(dest, post_process) {
+            panic!("Failed filling a buffer from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to fill multiple buffers, each with a key derived from an [indexed child](Self::index())
+    /// of this tree. That is, for each `(index, dest)` pair, the result is equivalent to
+    /// `tree.index(index).try_fill(dest)`.
+    ///
+    /// Each buffer costs as much as the equivalent `tree.index(index).try_fill(dest)` call,
+    /// i.e., two full derivations (one for the child seed and one for the key). No Blake2b state
+    /// is shared among buffers: the salt and personalization are a part of the Blake2b
+    /// initialization block, so each derivation starts from scratch. The method only saves
+    /// on instantiating intermediate child trees; child seeds are kept on stack and are zeroed
+    /// after use.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered, in the same cases as [`Self::try_fill()`].
+    /// Buffers preceding the failing one remain filled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{AsByteSliceMut, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut key = [0_u8; 32];
+    /// let mut mac_key = [0_u64; 4];
+    /// tree.try_fill_many([
+    ///     (0, &mut key as &mut dyn AsByteSliceMut),
+    ///     (1, &mut mac_key),
+    /// ])?;
+    ///
+    /// let mut expected_key = [0_u8; 32];
+    /// tree.index(0).fill(&mut expected_key);
+    /// assert_eq!(key, expected_key);
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_fill_many<'a>(
+        &self,
+        dests: impl IntoIterator<Item = (u64, &'a mut dyn AsByteSliceMut)>,
+    ) -> Result<(), FillError> {
+        let mut child_seed = [0_u8; SEED_LEN];
+        let mut result = Ok(());
+        for (index, dest) in dests {
+            self.derive_index_seed(index, &mut child_seed);
+            result = Self::fill_from_seed(&child_seed, dest);
+            if result.is_err() {
+                break;
+            }
+        }
+        child_seed.zeroize();
+        result
+    }
+
+    /// Fills multiple buffers, each with a key derived from an indexed child of this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill_many()`] returns an error.
+    #[track_caller]
+    pub fn fill_many<'a>(
+        &self,
+        dests: impl IntoIterator<Item = (u64, &'a mut dyn AsByteSliceMut)>,
+    ) {
+        if let Err(err) = self.try_fill_many(dests) {
+            panic!("Failed filling buffers from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to fill each element of a slice with a key derived from the [indexed child](Self::index())
+    /// of this tree with the element position; i.e., element `i` is filled as with
+    /// `tree.index(i).try_fill(&mut dests[i])`. This is useful for arrays of independent secrets,
+    /// and can be used to fill slices larger than 64 bytes.
+    ///
+    /// Unlike filling from the [CSPRNG](Self::rng()), each element is derived independently;
+    /// thus, any element can be re-derived on its own (e.g., using [`Self::indices()`]),
+    /// and leakage of an element does not reveal anything about other elements.
+    /// Like with [`Self::try_fill_many()`], no intermediate child trees are instantiated.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same cases as [`Self::try_fill_many()`]; e.g., if the byte size
+    /// of an element is not in `16..=64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut keys = [[0_u8; 32]; 10];
+    /// tree.try_fill_indexed(&mut keys)?;
+    ///
+    /// for (i, child) in tree.indices(0..10) {
+    ///     let mut expected_key = [0_u8; 32];
+    ///     child.fill(&mut expected_key);
+    ///     assert_eq!(keys[i as usize], expected_key);
+    /// }
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_fill_indexed<T: AsByteSliceMut>(&self, dests: &mut [T]) -> Result<(), FillError> {
+        let dests = (0_u64..).zip(dests);
+        self.try_fill_many(dests.map(|(index, dest)| (index, dest as &mut dyn AsByteSliceMut)))
+    }
+
+    /// Fills each element of a slice with a key derived from the indexed child of this tree
+    /// with the element position. See [`Self::try_fill_indexed()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill_indexed()`] returns an error.
+    #[track_caller]
+    pub fn fill_indexed<T: AsByteSliceMut>(&self, dests: &mut [T]) {
+        if let Err(err) = self.try_fill_indexed(dests) {
+            panic!("Failed filling buffers from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to fill a tuple of heterogeneous buffers, each with a key derived from
+    /// an [indexed child](Self::index()) of this tree. The buffer at position `i`
+    /// in the tuple uses index `i`; i.e., the result is equivalent to calling
+    /// [`Self::try_fill_many()`] with indices `0, 1, ...`.
+    ///
+    /// Unlike `try_fill_many()`, this method does not require casting buffers
+    /// to trait objects. Tuples with up to 8 elements are supported. The method is purely
+    /// an ergonomic improvement; no keyed state is reused among buffers, so each buffer costs
+    /// as much as filling it from the indexed child separately.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same cases as [`Self::try_fill_many()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut encryption_key = [0_u8; 32];
+    /// let mut mac_key = [0_u64; 4];
+    /// let mut nonce_seed = 0_u128;
+    /// tree.try_fill_tuple((&mut encryption_key, &mut mac_key, &mut nonce_seed))?;
+    ///
+    /// let mut expected_mac_key = [0_u64; 4];
+    /// tree.index(1).fill(&mut expected_mac_key);
+    /// assert_eq!(mac_key, expected_mac_key);
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_fill_tuple<T: FillTuple>(&self, dests: T) -> Result<(), FillError> {
+        dests.try_fill_from(self)
+    }
+
+    /// Fills a tuple of heterogeneous buffers, each with a key derived from an indexed child
+    /// of this tree. See [`Self::try_fill_tuple()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill_tuple()`] returns an error.
+    #[track_caller]
+    pub fn fill_tuple<T: FillTuple>(&self, dests: T) {
+        if let Err(err) = self.try_fill_tuple(dests) {
+            panic!("Failed filling buffers from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to derive a key from the seed of this tree in the same way as libsodium's
+    /// [`crypto_kdf_derive_from_key`] with the seed used as the master key.
+    ///
+    /// This is a low-level primitive allowing to reproduce keys derived by libsodium-based
+    /// applications. Only 32-byte master keys are supported, both by libsodium
+    /// (`crypto_kdf_KEYBYTES`) and by this method; keys of other lengths should be converted
+    /// to a tree seed by other means (e.g., with [`Self::from_short_seed()`], which is
+    /// not libsodium-compatible). Unlike other derivation methods, the output is not converted
+    /// to little-endian byte order, and the tree is not consumed.
+    ///
+    /// Derivations performed by the crate itself use contexts listed in [`contexts::ALL`]
+    /// (e.g., [`Self::fill()`] is equivalent to `derive_raw(0, contexts::FILL_BYTES, _)`);
+    /// application-specific contexts should be distinct from them.
+    ///
+    /// [`crypto_kdf_derive_from_key`]: https://doc.libsodium.org/key_derivation
+    ///
+    /// # Errors
+    ///
+    /// Errors if `output` does not have length `16..=64` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut subkey = [0_u8; 32];
+    /// tree.try_derive_raw(1, *b"Examples", &mut subkey)?;
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_derive_raw(
+        &self,
+        subkey_id: u64,
+        context: [u8; CONTEXT_LEN],
+        output: &mut [u8],
+    ) -> Result<(), FillError> {
+        try_derive_key(
+            output,
+            Index::Number(subkey_id),
+            context,
+            self.derivation_seed(),
+        )
+    }
+
+    /// Derives a key from the seed of this tree in the same way as libsodium's
+    /// `crypto_kdf_derive_from_key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_derive_raw()`] returns an error.
+    #[track_caller]
+    pub fn derive_raw(&self, subkey_id: u64, context: [u8; CONTEXT_LEN], output: &mut [u8]) {
+        if let Err(err) = self.try_derive_raw(subkey_id, context, output) {
+            panic!("Failed deriving a key from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to derive a key parameterized by an arbitrary-length `info` byte string,
+    /// similar to the `info` parameter of [HKDF]. This can be used when migrating from
+    /// HKDF-centric designs; note that the derivation is **not** HKDF, but is based on Blake2b
+    /// like the other derivations in this crate.
+    ///
+    /// The salt for derivation is the unkeyed Blake2b-128 hash of `info` (no salt
+    /// or personalization), and the personalization is [`contexts::INFO`]. Hashing is performed
+    /// regardless of the `info` length, so that distinct `info` strings never map to
+    /// the same salt (e.g., as would be the case with zero-padding `b"a"` and `b"a\0"`).
+    /// As with [`Self::derive_raw()`], the output is not converted to little-endian byte order,
+    /// and the tree is not consumed.
+    ///
+    /// [HKDF]: https://datatracker.ietf.org/doc/html/rfc5869
+    ///
+    /// # Errors
+    ///
+    /// Errors if `output` does not have length `16..=64` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let mut key = [0_u8; 32];
+    /// tree.try_derive_info(b"my-app v1 session encryption key", &mut key)?;
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_derive_info(&self, info: &[u8], output: &mut [u8]) -> Result<(), FillError> {
+        try_derive_key(
+            output,
+            Index::Bytes(hash_to_salt(info)),
+            contexts::INFO,
+            self.derivation_seed(),
+        )
+    }
+
+    /// Derives a key parameterized by an arbitrary-length `info` byte string.
+    /// See [`Self::try_derive_info()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_derive_info()`] returns an error.
+    #[track_caller]
+    pub fn derive_info(&self, info: &[u8], output: &mut [u8]) {
+        if let Err(err) = self.try_derive_info(info, output) {
+            panic!("Failed deriving a key from `SecretTree`: {err}");
+        }
+    }
+
+    /// Tries to create a secret by instantiating a buffer and filling it with a key derived from
+    /// the seed of this tree. Essentially, this is a more high-level wrapper around
+    /// [`Self::try_fill()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` does not have length `16..=64` bytes. Use [`Self::rng()`]
+    /// if the buffer size may be outside these bounds, or if the secret must be derived
+    /// in a more complex way.
+    pub fn try_create_secret<T>(self) -> Result<SecretBox<T>, FillError>
+    where
+        T: AsByteSliceMut + Default + Zeroize,
+    {
+        let mut result = Ok(());
+        let secret = SecretBox::init_with_mut(|secret_value| {
+            result = self.try_fill(secret_value);
+        });
+        result?;
+        Ok(secret)
+    }
+
+    /// Creates a secret by instantiating a buffer and filling it with a key derived from
+    /// the seed of this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_create_secret()`] returns an error.
+    #[track_caller]
+    pub fn create_secret<T>(self) -> SecretBox<T>
+    where
+        T: AsByteSliceMut + Default + Zeroize,
+    {
+        match self.try_create_secret() {
+            Ok(secret) => secret,
+            Err(err) => panic!("Failed creating a secret from `SecretTree`: {err}"),
+        }
+    }
+
+    /// Tries to create a fixed number of secrets, each derived from an [indexed child](Self::index())
+    /// of this tree. The secret at position `i` is equal to `tree.index(i).create_secret()`.
+    /// Like with [`Self::try_fill_many()`], no intermediate child trees are instantiated,
+    /// but each secret still costs two full derivations.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same cases as [`Self::try_create_secret()`], i.e., if `T` does not have
+    /// length `16..=64` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{secrecy::{ExposeSecret, SecretBox}, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let [signing_key, encryption_key, mac_key, backup_key] =
+    ///     tree.try_create_secrets::<[u8; 32], 4>()?;
+    ///
+    /// let expected_key: SecretBox<[u8; 32]> = tree.index(1).create_secret();
+    /// assert_eq!(encryption_key.expose_secret(), expected_key.expose_secret());
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    pub fn try_create_secrets<T, const N: usize>(&self) -> Result<[SecretBox<T>; N], FillError>
+    where
+        T: AsByteSliceMut + Default + Zeroize,
+    {
+        let mut child_seed = [0_u8; SEED_LEN];
+        let mut result = Ok(());
+        let secrets = core::array::from_fn(|index| {
+            SecretBox::init_with_mut(|secret_value| {
+                if result.is_ok() {
+                    self.derive_index_seed(index as u64, &mut child_seed);
+                    result = Self::fill_from_seed(&child_seed, secret_value);
+                }
+            })
+        });
+        child_seed.zeroize();
+        result?;
+        Ok(secrets)
+    }
+
+    /// Creates a fixed number of secrets, each derived from an indexed child of this tree.
+    /// See [`Self::try_create_secrets()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_create_secrets()`] returns an error.
+    #[track_caller]
+    pub fn create_secrets<T, const N: usize>(&self) -> [SecretBox<T>; N]
+    where
+        T: AsByteSliceMut + Default + Zeroize,
+    {
+        match self.try_create_secrets() {
+            Ok(secrets) => secrets,
+            Err(err) => panic!("Failed creating secrets from `SecretTree`: {err}"),
+        }
+    }
+
+    /// Tries to create a secret byte buffer with the specified length, which may be known
+    /// only at runtime (e.g., come from a protocol field). The buffer is filled in the same way
+    /// as with [`Self::try_fill()`].
+    ///
+    /// # Security
+    ///
+    /// The buffer is allocated on the heap once, filled in place and zeroized on drop,
+    /// so no copies of the secret are left on the heap. Still, prefer [`Self::try_create_secret()`]
+    /// or [`Self::try_fill()`] with fixed-size buffers if the length is known at compile time:
+    /// they avoid allocation altogether.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `len` is outside `16..=64`. The length is checked before allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// # use secrecy::ExposeSecret;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let key_len = 48; // e.g., read from a protocol message
+    /// let key = tree.try_create_bytes(key_len)?;
+    /// assert_eq!(key.expose_secret().len(), key_len);
+    /// # Ok::<_, secret_tree::FillError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn try_create_bytes(self, len: usize) -> Result<secrecy::SecretSlice<u8>, FillError> {
+        kdf::check_key_len(len)?;
+        let mut bytes = alloc::vec![0_u8; len].into_boxed_slice();
+        self.try_fill(&mut *bytes)?;
+        Ok(secrecy::SecretSlice::from(bytes))
+    }
+
+    /// Creates a secret byte buffer with the specified length.
+    /// See [`Self::try_create_bytes()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_create_bytes()`] returns an error.
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub fn create_bytes(self, len: usize) -> secrecy::SecretSlice<u8> {
+        match self.try_create_bytes(len) {
+            Ok(secret) => secret,
+            Err(err) => panic!("Failed creating a secret from `SecretTree`: {err}"),
+        }
+    }
+
+    /// Derives an Ed25519 keypair from this tree. The 32-byte secret key is derived
+    /// in the same way as with `self.create_secret::<[u8; 32]>()`; the verifying (public) key
+    /// is computed from it and is returned alongside, so that it does not need to be recomputed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let (signing_key, verifying_key) =
+    ///     tree.child(Name::new("service")).into_ed25519_keypair();
+    /// assert_eq!(signing_key.verifying_key(), verifying_key);
+    /// ```
+    #[cfg(feature = "ed25519")]
+    pub fn into_ed25519_keypair(self) -> (ed25519::SigningKey, ed25519::VerifyingKey) {
+        let mut secret_key = Zeroizing::new([0_u8; 32]);
+        self.fill(&mut secret_key);
+        let signing_key = ed25519::SigningKey::from_bytes(&secret_key);
+        let verifying_key = signing_key.verifying_key();
+        (signing_key, verifying_key)
+    }
+
+    /// Derives a UUID from the child of this tree with the specified `index`. 16 bytes
+    /// are derived in the same way as with `self.index(index).fill(&mut [0_u8; 16])`;
+    /// version and variant bits are then overwritten to produce a well-formed
+    /// [version 4](https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-4) UUID.
+    ///
+    /// The UUID is fully determined by the tree seed and `index`, so it is only as unique
+    /// as the seed is secret; if the seed leaks, all derived UUIDs become predictable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let resources = tree.child(Name::new("resources"));
+    /// let id = resources.derive_uuid(42);
+    /// assert_eq!(id.get_version_num(), 4);
+    /// assert_eq!(id, resources.derive_uuid(42));
+    /// assert_ne!(id, resources.derive_uuid(43));
+    /// ```
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn derive_uuid(&self, index: u64) -> uuid::Uuid {
+        let mut bytes = [0_u8; 16];
+        self.index(index).fill(&mut bytes);
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    /// Tries to derive a key and a nonce for the AEAD cipher `C` from this tree. The key
+    /// and the nonce are derived from the tree seed with distinct personalizations
+    /// (see the [crate-level docs](crate#salt-and-personalization)); the nonce
+    /// is thus fully deterministic.
+    ///
+    /// # Security
+    ///
+    /// **Reusing a nonce with the same key is catastrophic for most AEAD ciphers**
+    /// (e.g., for AES-GCM and ChaCha20-Poly1305, it leaks the authentication key and
+    /// the XOR of plaintexts). Since the nonce is derived deterministically, **the returned
+    /// pair must be used to encrypt at most one message**, unless the cipher is explicitly
+    /// misuse-resistant (e.g., AES-GCM-SIV), in which case encrypting the same message twice
+    /// leaks only the fact of equality. Uniqueness of messages must be guaranteed
+    /// by the derivation path of this tree, e.g., by indexing the tree by a message sequence
+    /// number that is never reused. If this cannot be guaranteed, derive only the key
+    /// and generate nonces randomly or use a counter.
+    ///
+    /// The key and the nonce are derived into buffers that are zeroized after use (including
+    /// on errors), but the returned key and nonce are not zeroized on drop; it is the caller's
+    /// responsibility to zeroize them if necessary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cipher key does not have length `16..=64` bytes, or if
+    /// the nonce does not have length `1..=64` bytes.
+    #[cfg(feature = "aead")]
+    pub fn try_into_cipher_material<C: aead::AeadCore + aead::KeyInit>(
+        self,
+    ) -> Result<(aead::Key<C>, aead::Nonce<C>), FillError> {
+        use aead::generic_array::typenum::Unsigned as _;
+
+        // `GenericArray`s do not implement `Zeroize`, so we derive into zeroizing buffers
+        // and only copy the outputs to arrays when handing them to the caller.
+        let mut key = Zeroizing::new([0_u8; kdf::MAX_OUTPUT_LEN]);
+        let key = Self::cipher_material_buffer(&mut key, C::KeySize::USIZE)?;
+        try_derive_key(key, Index::None, contexts::AEAD_KEY, self.derivation_seed())?;
+
+        let mut nonce = Zeroizing::new([0_u8; kdf::MAX_OUTPUT_LEN]);
+        let nonce = Self::cipher_material_buffer(&mut nonce, C::NonceSize::USIZE)?;
+        let derive_nonce = if nonce.len() < kdf::MIN_SUPPORTED_OUTPUT_LEN {
+            try_derive_short_key
+        } else {
+            try_derive_key
+        };
+        derive_nonce(
+            nonce,
+            Index::None,
+            contexts::AEAD_NONCE,
+            self.derivation_seed(),
+        )?;
+        Ok((
+            aead::Key::<C>::clone_from_slice(key),
+            aead::Nonce::<C>::clone_from_slice(nonce),
+        ))
+    }
+
+    #[cfg(feature = "aead")]
+    fn cipher_material_buffer(
+        buffer: &mut [u8; kdf::MAX_OUTPUT_LEN],
+        len: usize,
+    ) -> Result<&mut [u8], FillError> {
+        buffer.get_mut(..len).ok_or(FillError::BufferTooLarge {
+            size: len,
+            max_supported_size: kdf::MAX_OUTPUT_LEN,
+        })
+    }
+
+    /// Derives a key and a nonce for the AEAD cipher `C` from this tree.
+    /// See [`Self::try_into_cipher_material()`] for details and **security caveats**.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_into_cipher_material()`] returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// use chacha20poly1305::{aead::{Aead, KeyInit}, ChaCha20Poly1305};
+    ///
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// // The message sequence number must never be reused for this tree.
+    /// let message_seq = 5;
+    /// let (key, nonce) = tree
+    ///     .child(Name::new("messages"))
+    ///     .index(message_seq)
+    ///     .into_cipher_material::<ChaCha20Poly1305>();
+    /// let cipher = ChaCha20Poly1305::new(&key);
+    /// let ciphertext = cipher.encrypt(&nonce, b"hello".as_slice()).unwrap();
+    /// # assert_eq!(cipher.decrypt(&nonce, ciphertext.as_slice()).unwrap(), b"hello");
+    /// ```
+    #[cfg(feature = "aead")]
+    #[track_caller]
+    pub fn into_cipher_material<C: aead::AeadCore + aead::KeyInit>(
+        self,
+    ) -> (aead::Key<C>, aead::Nonce<C>) {
+        match self.try_into_cipher_material::<C>() {
+            Ok(material) => material,
+            Err(err) => panic!("Failed deriving cipher material from `SecretTree`: {err}"),
+        }
+    }
+
+    // Derivation methods write child seeds directly into `Seed` containers, and zeroize all local
+    // buffers that may contain secret-derived data before returning. This should be preserved
+    // when adding new derivation methods or modifying existing ones.
+
+    /// Produces a child with the specified string identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn child(&self, name: Name) -> Self {
+        self.assert_depth();
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Bytes(name.0),
+                contexts::NAME,
+                self.derivation_seed(),
+            );
+        });
+        self.derive_child(Segment::Name(name), seed)
+    }
+
+    /// Produces a child identified by a short binary string (up to 15 bytes), such as
+    /// a binary device ID. The bytes are zero-padded to 15 bytes and followed by their length
+    /// to form the salt, with a personalization distinct from [named](Self::child())
+    /// and [indexed](Self::index()) children (see the [crate-level docs](crate#salt-and-personalization)).
+    /// Since the length is encoded, trailing zero bytes are significant: e.g., `b"ab"`
+    /// and `b"ab\0"` produce different children. Use [`Self::digest()`] for longer identifiers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is longer than [`RawId::MAX_LEN`] (15) bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let device_id = [0xde, 0xad, 0xbe, 0xef];
+    /// let device_tree = tree.child_raw(&device_id)?;
+    /// assert_ne!(device_tree, tree.child(Name::new("\u{de}\u{ad}")));
+    /// assert!(tree.child_raw(&[0; 16]).is_err());
+    /// # Ok::<_, secret_tree::LenError>(())
+    /// ```
+    #[track_caller]
+    pub fn child_raw(&self, bytes: &[u8]) -> Result<Self, LenError> {
+        Ok(self.child_bytes(RawId::new(bytes)?))
+    }
+
+    #[track_caller]
+    fn child_bytes(&self, id: RawId) -> Self {
+        self.assert_depth();
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Bytes(id.to_salt()),
+                contexts::RAW,
+                self.derivation_seed(),
+            );
+        });
+        self.derive_child(Segment::Bytes(id), seed)
+    }
+
+    /// Lazily produces children with the specified names, yielding them together with
+    /// the corresponding name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// const NAMES: &[Name] = &[Name::new("signing"), Name::new("encryption")];
+    ///
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// for (name, child) in tree.named_children(NAMES) {
+    ///     let key: [u8; 32] = *child.create_secret().expose_secret();
+    ///     // Use the key...
+    /// #   let mut expected_key = [0_u8; 32];
+    /// #   tree.child(name).fill(&mut expected_key);
+    /// #   assert_eq!(key, expected_key);
+    /// }
+    /// # use secrecy::ExposeSecret;
+    /// ```
+    pub fn named_children<'a>(
+        &'a self,
+        names: &'a [Name],
+    ) -> impl Iterator<Item = (Name, Self)> + 'a {
+        names.iter().map(move |&name| (name, self.child(name)))
+    }
+
+    /// Lazily produces children with indices in the specified range, yielding them together
+    /// with the corresponding index. Each child is equivalent to the one produced
+    /// by [`Self::index()`].
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    pub fn indices(&self, indices: Range<u64>) -> impl Iterator<Item = (u64, Self)> + '_ {
+        indices.map(move |index| (index, self.index(index)))
+    }
+
+    /// Selects `k` out of `n` indexed children of this tree. The selection is deterministic:
+    /// the returned children have indices `0..k` (in this order), i.e., they are equivalent
+    /// to the first `k` children yielded by [`Self::indices()`]`(0..n)`.
+    ///
+    /// To select children pseudo-randomly, shuffle indices with the RNG derived from
+    /// a dedicated child tree (e.g., via `SecretTree::shuffle()` if the `rand` feature is enabled).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `k > n`.
+    /// - Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let shares = tree.child(Name::new("shares")).select(5, 3);
+    /// assert_eq!(shares.len(), 3);
+    /// assert_eq!(shares[2], tree.child(Name::new("shares")).index(2));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub fn select(&self, n: u64, k: usize) -> alloc::vec::Vec<Self> {
+        assert!(
+            u64::try_from(k).is_ok_and(|k| k <= n),
+            "Cannot select {k} children out of {n}"
+        );
+        self.indices(0..n).take(k).map(|(_, child)| child).collect()
+    }
+
+    /// Lazily produces indexed children of the named child of this tree. The output is equivalent
+    /// to `tree.child(name).indices(indices)`, but the returned iterator does not borrow
+    /// this tree. The named child is derived once, on the method call.
+    ///
+    /// Note that each indexed child still requires a separate Blake2b invocation, since
+    /// the index is a part of the Blake2b initialization block and thus cannot be applied
+    /// to a precomputed hash state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    /// The check is performed both on the method call (for the named child) and by the iterator
+    /// (for the indexed children).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// for (i, account) in tree.indexed_children(Name::new("accounts"), 0..10) {
+    ///     let key: [u8; 32] = *account.create_secret().expose_secret();
+    ///     // Use the key...
+    /// #   let mut expected_key = [0_u8; 32];
+    /// #   tree.child(Name::new("accounts")).index(i).fill(&mut expected_key);
+    /// #   assert_eq!(key, expected_key);
+    /// }
+    /// # use secrecy::ExposeSecret;
+    /// ```
+    #[track_caller]
+    pub fn indexed_children(
+        &self,
+        name: Name,
+        indices: Range<u64>,
+    ) -> impl Iterator<Item = (u64, Self)> {
+        let parent = self.child(name);
+        indices.map(move |index| (index, parent.index(index)))
+    }
+
+    /// Produces a child with the name specified as a string. This is a runtime counterpart
+    /// of `tree.child(Name::new(name))`: the name is validated using the [`FromStr`]
+    /// implementation for [`Name`] rather than panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is not a valid [`Name`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let category = String::from("accounts");
+    /// let child = tree.try_child_str(&category)?;
+    /// assert_eq!(child, tree.child(Name::new("accounts")));
+    /// assert!(tree.try_child_str("name that is too long").is_err());
+    /// # Ok::<_, secret_tree::NameError>(())
+    /// ```
+    #[track_caller]
+    pub fn try_child_str(&self, name: impl AsRef<str>) -> Result<Self, NameError> {
+        let name = name.as_ref().parse()?;
+        Ok(self.child(name))
+    }
+
+    /// Tries to produce a child with the specified string identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    pub fn try_child(&self, name: Name) -> Result<Self, DepthError> {
+        self.check_depth()?;
+        Ok(self.child(name))
+    }
+
+    /// Tries to produce a child with the specified integer index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    pub fn try_index(&self, index: u64) -> Result<Self, DepthError> {
+        self.check_depth()?;
+        Ok(self.index(index))
+    }
+
+    /// Produces a child with the specified integer index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn index(&self, index: u64) -> Self {
+        self.assert_depth();
+        let seed = Seed::init_with(|child_seed| self.derive_index_seed(index, child_seed));
+        self.derive_child(Segment::Index(index), seed)
+    }
+
+    /// Produces a child with the specified 2-dimensional index, e.g. a `(row, column)` position
+    /// in a grid. The child is derived in a single step: both indices are packed into the salt
+    /// (`LittleEndian(a) || LittleEndian(b)`), with a dedicated personalization; see
+    /// the [crate-level docs](crate#salt-and-personalization) for details.
+    /// Thus, children for different `(a, b)` pairs never collide, and the returned child
+    /// is different from both `self.index(a).index(b)` and any child produced by [`Self::index()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let grid = tree.child(Name::new("grid"));
+    /// let cell = grid.index2(3, 5);
+    /// assert_ne!(cell, grid.index2(5, 3));
+    /// assert_ne!(cell, grid.index(3).index(5));
+    /// ```
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn index2(&self, a: u64, b: u64) -> Self {
+        self.assert_depth();
+        let mut salt = [0_u8; SALT_LEN];
+        salt[..8].copy_from_slice(&a.to_le_bytes());
+        salt[8..].copy_from_slice(&b.to_le_bytes());
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Bytes(salt),
+                contexts::INDEX2,
+                self.derivation_seed(),
+            );
+        });
+        self.derive_child(Segment::Index2(a, b), seed)
+    }
+
+    /// Produces a child with the specified `usize` index, e.g., a position in a slice.
+    /// This is exactly `self.index(index as u64)`; since `usize` is never wider than 64 bits
+    /// on supported platforms, the widening is lossless and the output is the same
+    /// regardless of the pointer width.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn index_usize(&self, index: usize) -> Self {
+        const _: () = assert!(mem::size_of::<usize>() <= mem::size_of::<u64>());
+        self.index(index as u64)
+    }
+
+    /// Produces a child with the specified 32-byte digest (e.g., an output of SHA-256,
+    /// SHA3-256 or Keccak256 hash functions).
+    ///
+    /// This method can be used for arbitrarily-sized keys by first digesting them
+    /// with a collision-resistant hash function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn digest(&self, digest: &[u8; 32]) -> Self {
+        self.assert_depth();
+        // The digest may be derived from secret data, so we zeroize its local copies.
+        // The intermediate seed is zeroized on drop by `Seed`.
+        let mut first_half_of_digest = [0_u8; SALT_LEN];
+        first_half_of_digest.copy_from_slice(&digest[0..SALT_LEN]);
+        let mut second_half_of_digest = [0_u8; SALT_LEN];
+        second_half_of_digest.copy_from_slice(&digest[SALT_LEN..]);
+
+        let intermediate_seed = Seed::init_with(|intermediate_seed| {
+            derive_key(
+                intermediate_seed,
+                Index::Bytes(first_half_of_digest),
+                contexts::DIGEST_START,
+                self.derivation_seed(),
+            );
+        });
+
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Bytes(second_half_of_digest),
+                contexts::DIGEST_END,
+                intermediate_seed.expose_secret(),
+            );
+        });
+        first_half_of_digest.zeroize();
+        second_half_of_digest.zeroize();
+        // The digest may be secret, so only a placeholder is recorded in the path.
+        self.derive_recorded_child(RecordedSegment::RedactedDigest, seed)
+    }
+
+    /// Splits this tree into two independent trees: one intended for deriving a fixed-size key
+    /// (e.g., with [`Self::fill()`]), and another one for deriving an RNG (e.g., a nonce source).
+    /// This allows using both derivation kinds for the same logical node without creating
+    /// named children.
+    ///
+    /// Both trees are children of this tree (i.e., they have increased [depth](Self::depth()));
+    /// they are recorded in derivation paths as [`Segment::Split`]. See the [crate-level docs](crate)
+    /// for the salt and personalization used for derivation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::{thread_rng, Rng};
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let (key_tree, rng_tree) = tree.child(Name::new("session")).split();
+    /// let mut key = [0_u8; 32];
+    /// key_tree.fill(&mut key);
+    /// let mut nonce_source = rng_tree.rng();
+    /// let nonce: [u8; 12] = nonce_source.gen();
+    /// ```
+    #[track_caller]
+    #[must_use = "derived child trees must be used to produce secrets"]
+    pub fn split(self) -> (Self, Self) {
+        (
+            self.split_half(SplitHalf::Keys),
+            self.split_half(SplitHalf::Rng),
+        )
+    }
+
+    #[track_caller]
+    fn split_half(&self, half: SplitHalf) -> Self {
+        self.assert_depth();
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Number(half.index()),
+                contexts::SPLIT,
+                self.derivation_seed(),
+            );
+        });
+        self.derive_child(Segment::Split(half), seed)
+    }
+
+    /// Rotates this tree to the specified generation. This allows to replace all secrets
+    /// derived from the tree (e.g., when a derivation scheme changes) by bumping a single number,
+    /// without reusing derivation paths for different purposes.
+    ///
+    /// Generation 0 corresponds to this tree itself, so existing deployments are unaffected
+    /// when starting to use rotation. Each generation `>= 1` is an independent child of this tree
+    /// (i.e., it has increased [depth](Self::depth())); it is recorded in derivation paths
+    /// as [`Segment::Rotation`]. See the [crate-level docs](crate) for the salt
+    /// and personalization used for derivation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `generation >= 1` and the [maximum depth](Self::with_max_depth()) of the tree
+    /// is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let signing = tree.child(Name::new("signing"));
+    /// assert_eq!(signing.rotate(0), signing);
+    /// let rotated = signing.rotate(1);
+    /// assert_ne!(rotated, signing);
+    /// assert_eq!(rotated.depth(), signing.depth() + 1);
+    /// ```
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn rotate(&self, generation: u32) -> Self {
+        if generation == 0 {
+            return self.duplicate();
+        }
+
+        self.assert_depth();
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Number(generation.into()),
+                contexts::ROTATION,
+                self.derivation_seed(),
+            );
+        });
+        self.derive_child(Segment::Rotation(generation), seed)
+    }
+
+    /// Produces a child cryptographically bound to non-secret associated data `aad`,
+    /// e.g., a protocol version string. This is similar to associated data in AEAD ciphers:
+    /// secrets derived from the child depend on `aad`, but `aad` is not meant to structure
+    /// the secret hierarchy (unlike [names](Self::child()) or [indices](Self::index())).
+    ///
+    /// `aad` is considered public. It is hashed with unkeyed Blake2b-128, and the hash is used
+    /// as the salt with a dedicated personalization (see the
+    /// [crate-level docs](crate#salt-and-personalization)), so that the child is distinct
+    /// from children produced with other methods for any `aad`. The hash is recorded in
+    /// derivation paths as [`Segment::Binding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let session_keys = tree.child(Name::new("session")).bind(b"my-protocol v2");
+    /// assert_ne!(session_keys, tree.child(Name::new("session")).bind(b"my-protocol v1"));
+    /// ```
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn bind(&self, aad: &[u8]) -> Self {
+        self.bind_hash(hash_to_salt(aad))
+    }
+
+    #[track_caller]
+    fn bind_hash(&self, aad_hash: [u8; SALT_LEN]) -> Self {
+        self.assert_depth();
+        let seed = Seed::init_with(|child_seed| {
+            derive_key(
+                child_seed,
+                Index::Bytes(aad_hash),
+                contexts::BINDING,
+                self.derivation_seed(),
+            );
+        });
+        self.derive_child(Segment::Binding(aad_hash), seed)
+    }
+
+    /// Produces a child with the specified digest supplied as a byte slice. This is a more
+    /// convenient alternative to [`Self::digest()`] if the digest is not available as
+    /// a fixed-size array (e.g., it is obtained from `Sha256::finalize()`); the derivation
+    /// is exactly the same.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `digest` is not 32 bytes long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let digest: Vec<u8> = // ...
+    /// #   vec![1; 32];
+    /// let child = tree.digest_slice(&digest)?;
+    /// assert_eq!(child, tree.digest(&[1; 32]));
+    /// assert!(tree.digest_slice(&digest[..31]).is_err());
+    /// # Ok::<_, secret_tree::LenError>(())
+    /// ```
+    pub fn digest_slice(&self, digest: &[u8]) -> Result<Self, LenError> {
+        let digest: &[u8; 32] = digest.try_into().map_err(|_| LenError {
+            actual: digest.len(),
+            min: 32,
+            max: 32,
+        })?;
+        Ok(self.digest(digest))
+    }
+
+    /// Tries to produce a child with the specified 32-byte digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    pub fn try_digest(&self, digest: &[u8; 32]) -> Result<Self, DepthError> {
+        self.check_depth()?;
+        Ok(self.digest(digest))
+    }
+
+    /// Produces a descendant of this tree by sequentially applying [`Self::digest()`]
+    /// for each of the supplied digests, e.g., following a Merkle path. That is,
+    /// `tree.digest_chain([a, b, c])` is equivalent to `tree.digest(&a).digest(&b).digest(&c)`.
+    /// If `digests` is empty, returns a copy of this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let digests = [[1; 32], [2; 32], [3; 32]];
+    /// let descendant = tree.digest_chain(digests);
+    /// assert_eq!(
+    ///     descendant,
+    ///     tree.digest(&[1; 32]).digest(&[2; 32]).digest(&[3; 32])
+    /// );
+    /// ```
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn digest_chain(&self, digests: impl IntoIterator<Item = [u8; 32]>) -> Self {
+        let mut digests = digests.into_iter();
+        let Some(first_digest) = digests.next() else {
+            return self.duplicate();
+        };
+        digests.fold(self.digest(&first_digest), |tree, digest| {
+            tree.digest(&digest)
+        })
+    }
+
+    /// Derives a descendant of this tree by following a `/`-separated path,
+    /// such as `accounts/3/signing`.
+    ///
+    /// # Path syntax
+    ///
+    /// Each segment of the path is interpreted as follows:
+    ///
+    /// - `!0` and `!1` are the key and RNG trees produced by [splitting](Self::split()).
+    /// - Otherwise, `@` followed by decimal digits is a [rotated tree](Self::rotate()).
+    /// - Otherwise, `#` followed by an even number of hex digits (up to 32) is a child
+    ///   [identified by bytes](Self::child_raw()).
+    /// - Otherwise, `&` followed by 32 hex digits is a child [bound](Self::bind()) to associated
+    ///   data with the specified hash.
+    /// - Otherwise, a segment consisting of 64 hex digits is a [digest](Self::digest()).
+    /// - Otherwise, a segment consisting of decimal digits is an [index](Self::index()).
+    /// - Otherwise, two groups of decimal digits separated by `,` (e.g., `3,5`)
+    ///   are a [2D index](Self::index2()).
+    /// - Otherwise, the segment is a [name](Self::child()).
+    ///
+    /// This is consistent with how [`Segment`]s are displayed. Consequently, names consisting
+    /// of digits only, names `!0` and `!1`, names consisting of `@` followed by digits,
+    /// names consisting of two groups of digits separated by `,`,
+    /// names consisting of `#` followed by an even number of hex digits,
+    /// names consisting of `&` followed by 32 hex digits,
+    /// or names containing `/` chars cannot be expressed in paths. An empty path
+    /// corresponds to the tree itself; empty segments are not allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is invalid (see [`Self::validate_path()`]), or if
+    /// the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let child = tree.derive_path("accounts/3/signing")?;
+    /// let expected_child = tree
+    ///     .child(Name::new("accounts"))
+    ///     .index(3)
+    ///     .child(Name::new("signing"));
+    /// assert_eq!(child, expected_child);
+    /// # Ok::<_, secret_tree::PathError>(())
+    /// ```
+    pub fn derive_path(&self, path: &str) -> Result<Self, PathError> {
+        let mut segments = parse_path(path);
+        let Some(first_segment) = segments.next() else {
+            return Ok(self.duplicate());
+        };
+        let mut tree = self
+            .try_derive_segment(first_segment?)
+            .map_err(PathError::Depth)?;
+        for segment in segments {
+            tree = tree
+                .try_derive_segment(segment?)
+                .map_err(PathError::Depth)?;
+        }
+        Ok(tree)
+    }
+
+    /// Validates a `/`-separated derivation path without deriving any secrets.
+    /// This can be used to check paths, e.g., ones read from the app config, in advance.
+    /// Validation uses the same parser as [`Self::derive_path()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first encountered error if the path is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// assert!(SecretTree::validate_path("accounts/3/signing").is_ok());
+    /// assert!(SecretTree::validate_path("accounts//signing").is_err());
+    /// ```
+    pub fn validate_path(path: &str) -> Result<(), PathError> {
+        parse_path(path).try_for_each(|segment| segment.map(drop))
+    }
+
+    /// Tries to derive a child by applying a single path [`Segment`]. This is the primitive
+    /// underlying [`Self::derive_path()`] and `DerivationScript`;
+    /// it can be used to implement custom traversal logic. The output is equivalent
+    /// to calling the method corresponding to the segment, such as [`Self::child()`]
+    /// for [`Segment::Name`] or [`Self::index()`] for [`Segment::Index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    pub fn try_derive_segment(&self, segment: Segment) -> Result<Self, DepthError> {
+        if segment == Segment::Rotation(0) {
+            // Rotation to generation 0 is a no-op, which never exceeds the maximum depth.
+            return Ok(self.duplicate());
+        }
+        self.check_depth()?;
+        Ok(match segment {
+            Segment::Name(name) => self.child(name),
+            Segment::Index(index) => self.index(index),
+            Segment::Index2(a, b) => self.index2(a, b),
+            Segment::Bytes(id) => self.child_bytes(id),
+            Segment::Digest(digest) => self.digest(&digest),
+            Segment::Binding(aad_hash) => self.bind_hash(aad_hash),
+            Segment::Split(half) => self.split_half(half),
+            Segment::Rotation(generation) => self.rotate(generation),
+        })
+    }
+
+    /// Derives a child by applying a single path [`Segment`].
+    /// See [`Self::try_derive_segment()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](Self::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn derive_segment(&self, segment: Segment) -> Self {
+        match self.try_derive_segment(segment) {
+            Ok(tree) => tree,
+            Err(err) => panic!("Failed deriving a child from `SecretTree`: {err}"),
+        }
+    }
+
+    /// Derives the seed of the child obtained by applying a single path [`Segment`].
+    /// This allows generic code to derive and extract seeds uniformly regardless
+    /// of the segment kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Self::derive_segment()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree, Segment};
+    /// # use rand::thread_rng;
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let seeds = [Segment::Name(Name::new("accounts")), Segment::Index(3)]
+    ///     .map(|segment| tree.derive_secret_seed(segment));
+    /// assert_eq!(
+    ///     seeds[1].expose_secret(),
+    ///     tree.index(3).seed().expose_secret()
+    /// );
+    /// ```
+    #[track_caller]
+    pub fn derive_secret_seed(&self, segment: Segment) -> Seed {
+        self.derive_segment(segment).seed
+    }
+
+    /// Selects one of two trees in constant time: returns a copy of `a` if `choice` is false,
+    /// and a copy of `b` if `choice` is true. The seed bytes are selected without data-dependent
+    /// branching or memory accesses.
+    ///
+    /// Since tree metadata could reveal which tree was selected, the returned tree is
+    /// treated as a root: it has zero [depth](Self::depth()), no maximum depth and (if
+    /// the `track-path` feature is enabled) an empty derivation path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::{Name, SecretTree};
+    /// # use rand::thread_rng;
+    /// use subtle::Choice;
+    ///
+    /// let tree = SecretTree::new(&mut thread_rng());
+    /// let (a, b) = (tree.child(Name::new("a")), tree.child(Name::new("b")));
+    /// let secret_bit = Choice::from(1);
+    /// let selected = SecretTree::conditional_select(&a, &b, secret_bit);
+    /// assert_eq!(selected, b);
+    /// ```
+    #[cfg(feature = "subtle")]
+    pub fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+        use subtle::ConditionallySelectable as _;
+
+        let (a_seed, b_seed) = (a.seed.expose_secret(), b.seed.expose_secret());
+        let seed = Seed::init_with(|seed| {
+            for ((dest, a_byte), b_byte) in seed.iter_mut().zip(a_seed).zip(b_seed) {
+                *dest = u8::conditional_select(a_byte, b_byte, choice);
+            }
+        });
+        Self::from_seed_unchecked(seed)
+    }
+}
+
+/// Compares byte arrays in constant time. All constant-time comparisons in the crate
+/// should use this helper.
+fn ct_eq<const N: usize>(lhs: &[u8; N], rhs: &[u8; N]) -> bool {
+    let diff = lhs
+        .iter()
+        .zip(rhs)
+        .fold(0_u8, |acc, (&x, &y)| acc | (x ^ y));
+    diff == 0
+}
+
+/// Compares trees by their seeds in constant time. Other tree properties (e.g., its depth)
+/// are not compared.
+impl PartialEq for SecretTree {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(self.seed.expose_secret(), other.seed.expose_secret())
+    }
+}
+
+impl Eq for SecretTree {}
+
+/// Displays the tree as `SecretTree(<tree-id>)`, where `<tree-id>` is the hex-encoded
+/// [`TreeId`] of the tree. The seed is never rendered, so the output can be safely logged.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::SecretTree;
+/// # use rand::thread_rng;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// assert_eq!(tree.to_string(), format!("SecretTree({})", tree.id()));
+/// ```
+impl fmt::Display for SecretTree {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "SecretTree({})", self.id())
+    }
+}
+
+/// Hashes the [`TreeId`] of the tree rather than its seed. Thus, the hasher
+/// does not learn the seed, and trees can be used as keys in hash maps (e.g., for memoization).
+impl Hash for SecretTree {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+/// Creates a tree from the seed. Equivalent to [`SecretTree::from_seed()`]; in particular,
+/// in debug builds, panics if the seed consists of zero bytes only.
+impl From<Seed> for SecretTree {
+    #[track_caller]
+    fn from(seed: Seed) -> Self {
+        Self::from_seed(seed)
+    }
+}
+
+/// Extracts the seed from the tree. Equivalent to [`SecretTree::into_seed()`]; the seed
+/// is moved rather than copied.
+impl From<SecretTree> for Seed {
+    fn from(tree: SecretTree) -> Self {
+        tree.into_seed()
+    }
+}
+
+/// Restores a tree from the seed specified as a byte vector. Unlike [`SecretTree::from_slice()`],
+/// this consumes the vector and zeroizes its contents (including spare capacity) before
+/// dropping it, so that no copy of the seed lingers in memory.
+///
+/// # Errors
+///
+/// Returns an error if the vector has an invalid length (not [`SEED_LEN`]). The vector
+/// is zeroized in this case as well.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`SecretTree::from_seed()`]. The vector is zeroized
+/// before the check.
+#[cfg(feature = "alloc")]
+impl TryFrom<alloc::vec::Vec<u8>> for SecretTree {
+    type Error = LenError;
+
+    #[track_caller]
+    fn try_from(mut bytes: alloc::vec::Vec<u8>) -> Result<Self, Self::Error> {
+        let len = bytes.len();
+        let seed = (len == SEED_LEN).then(|| {
+            Seed::init_with(|seed| {
+                seed.copy_from_slice(&bytes);
+            })
+        });
+        // Zeroize the vector before `from_seed()`, which may panic.
+        bytes.zeroize();
+
+        let seed = seed.ok_or(LenError {
+            actual: len,
+            min: SEED_LEN,
+            max: SEED_LEN,
+        })?;
+        Ok(Self::from_seed(seed))
+    }
+}
+
+/// Errors that can occur when calling [`SecretTree::try_fill()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FillError {
+    /// The supplied buffer is too small to be filled.
+    BufferTooSmall {
+        /// Byte size of the supplied buffer.
+        size: usize,
+        /// Minimum byte size for supported buffers.
+        min_supported_size: usize,
+    },
+    /// The supplied buffer is too large to be filled.
+    BufferTooLarge {
+        /// Byte size of the supplied buffer.
+        size: usize,
+        /// Maximum byte size for supported buffers.
+        max_supported_size: usize,
+    },
+}
+
+impl FillError {
+    /// Returns the byte size of the supplied buffer.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::BufferTooSmall { size, .. } | Self::BufferTooLarge { size, .. } => *size,
+        }
+    }
+
+    /// Returns the violated bound on the buffer size in bytes, i.e., the minimum supported size
+    /// if the buffer [is too small](Self::is_too_small()), or the maximum supported size
+    /// if it [is too large](Self::is_too_large()).
+    pub fn bound(&self) -> usize {
+        match self {
+            Self::BufferTooSmall {
+                min_supported_size, ..
+            } => *min_supported_size,
+            Self::BufferTooLarge {
+                max_supported_size, ..
+            } => *max_supported_size,
+        }
+    }
+
+    /// Checks whether this error is caused by a buffer that is too small.
+    pub fn is_too_small(&self) -> bool {
+        matches!(self, Self::BufferTooSmall { .. })
+    }
+
+    /// Checks whether this error is caused by a buffer that is too large.
+    pub fn is_too_large(&self) -> bool {
+        matches!(self, Self::BufferTooLarge { .. })
+    }
+}
+
+impl fmt::Display for FillError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall {
+                size,
+                min_supported_size,
+            } => {
+                write!(
+                    formatter,
+                    "supplied buffer ({size} bytes) is too small to be filled; \
+                     min supported size is {min_supported_size} bytes"
+                )
+            }
+
+            Self::BufferTooLarge {
+                size,
+                max_supported_size,
+            } => {
+                write!(
+                    formatter,
+                    "supplied buffer ({size} bytes) is too large to be filled; \
+                     max supported size is {max_supported_size} bytes"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FillError {}
+
+/// Error returned when a byte slice supplied to a `SecretTree` method has an unsupported length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LenError {
+    /// Actual byte length of the slice.
+    pub actual: usize,
+    /// Minimum supported byte length.
+    pub min: usize,
+    /// Maximum supported byte length.
+    pub max: usize,
+}
+
+impl fmt::Display for LenError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { actual, min, max } = self;
+        if min == max {
+            write!(
+                formatter,
+                "unsupported byte length {actual}; expected {min} bytes"
+            )
+        } else {
+            write!(
+                formatter,
+                "unsupported byte length {actual}; expected {min}..={max} bytes"
+            )
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LenError {}
+
+/// Errors that can occur when checking a [`Seed`], e.g., in [`SecretTree::from_seed_checked()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SeedError {
+    /// The seed consists of zero bytes only, which usually indicates an uninitialized buffer.
+    WeakSeed,
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::WeakSeed => "seed consists of zero bytes only",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SeedError {}
+
+/// Error returned when deriving a child would exceed the maximum depth set
+/// with [`SecretTree::with_max_depth()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DepthError {
+    /// Maximum allowed depth of the tree hierarchy, counted from the root.
+    pub max_depth: usize,
+}
+
+impl fmt::Display for DepthError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "maximum derivation depth ({}) exceeded",
+            self.max_depth
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DepthError {}
+
+/// Name of a child [`SecretTree`].
+///
+/// Used in [`SecretTree::child()`]; see its documentation for more context.
+///
+/// An original `str` can be extracted from `Name` using [`AsRef`] / [`Display`](fmt::Display)
+/// implementations:
+///
+/// ```
+/// # use secret_tree::Name;
+/// const NAME: Name = Name::new("test_name");
+/// assert_eq!(NAME.as_ref(), "test_name");
+/// assert_eq!(NAME.to_string(), "test_name");
+/// ```
+///
+/// # Comparisons
+///
+/// The [`PartialEq`] implementation is not constant-time. This is fine in the common case when
+/// names are hard-coded in the application, but may leak information via timing if the set
+/// of names is secret (e.g., names are per-user labels). For such cases, `Name` implements
+/// [`ConstantTimeEq`] if the `subtle` crate feature is enabled;
+/// it compares the entire zero-padded 16-byte buffers.
+///
+/// [`ConstantTimeEq`]: https://docs.rs/subtle/2/subtle/trait.ConstantTimeEq.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Name([u8; SALT_LEN]);
+
+impl Name {
+    /// Creates a new `Name`.
+    ///
+    /// The supplied string must be no more than [`MAX_NAME_LEN`] bytes in length
+    /// and must not contain null chars `'\0'`.
+    ///
+    /// This is a constant method, which perform all relevant checks during compilation in
+    /// a constant context:
+    ///
+    /// ```
+    /// # use secret_tree::Name;
+    /// const NAME: Name = Name::new("some_name");
+    /// ```
+    ///
+    /// For example, this won't compile since the name is too long (17 chars):
+    ///
+    /// ```compile_fail
+    /// # use secret_tree::Name;
+    /// const OVERLY_LONG_NAME: Name = Name::new("Overly long name!");
+    /// ```
+    ///
+    /// ...And this won't compile because the name contains a `\0` char:
+    ///
+    /// ```compile_fail
+    /// # use secret_tree::Name;
+    /// const NAME_WITH_ZERO_CHARS: Name = Name::new("12\03");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is overly long or contains null chars.
+    /// Use the [`FromStr`] implementation for a fallible / non-panicking alternative;
+    /// it should be preferred for names not known at compile time (e.g., read from
+    /// a config or provided by the user).
+    #[track_caller]
+    pub const fn new(name: &str) -> Self {
+        let bytes = name.as_bytes();
+        assert!(
+            bytes.len() <= SALT_LEN,
+            "name is too long (should be <=16 bytes)"
+        );
+
+        let mut i = 0;
+        let mut buffer = [0_u8; SALT_LEN];
+        while i < name.len() {
+            assert!(bytes[i] != 0, "name contains a null char");
+            buffer[i] = bytes[i];
+            i += 1;
+        }
+        Name(buffer)
+    }
+
+    /// Creates a name from a fixed-size byte array, e.g., a binary constant. The output
+    /// is the same as for [`Self::new()`] applied to the UTF-8 string represented by `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is longer than 16 bytes, is not valid UTF-8, or contains null chars.
+    /// If called in a constant context, these conditions are checked at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::Name;
+    /// const NAME: Name = Name::from_array(b"protocol_v1");
+    /// assert_eq!(NAME, Name::new("protocol_v1"));
+    /// ```
+    ///
+    /// Invalid UTF-8 is rejected:
+    ///
+    /// ```compile_fail
+    /// # use secret_tree::Name;
+    /// const INVALID_NAME: Name = Name::from_array(&[0xff, 0xfe]);
+    /// ```
+    #[track_caller]
+    pub const fn from_array<const N: usize>(bytes: &[u8; N]) -> Self {
+        match str::from_utf8(bytes) {
+            Ok(name) => Self::new(name),
+            Err(_) => panic!("name is not valid UTF-8"),
+        }
+    }
+
+    /// Concatenates the supplied string parts into a name. The resulting name is built
+    /// on the stack and is validated in the same way as in the [`FromStr`] implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the concatenated name is too long or contains null chars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::Name;
+    /// let user_id = 42;
+    /// let name = Name::try_concat(&["user", &user_id.to_string()])?;
+    /// assert_eq!(name, Name::new("user42"));
+    /// # Ok::<_, secret_tree::NameError>(())
+    /// ```
+    pub fn try_concat(parts: &[&str]) -> Result<Self, NameError> {
+        Self::try_join(parts, "")
+    }
+
+    /// Joins the supplied string parts with the specified separator into a name.
+    /// See [`Self::try_concat()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the joined name is too long or contains null chars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::Name;
+    /// let name = Name::try_join(&["user", "keys"], ".")?;
+    /// assert_eq!(name, Name::new("user.keys"));
+    /// assert!(Name::try_join(&["overly_long", "name"], "::").is_err());
+    /// # Ok::<_, secret_tree::NameError>(())
+    /// ```
+    pub fn try_join(parts: &[&str], separator: &str) -> Result<Self, NameError> {
+        fn append(
+            bytes: &mut [u8; SALT_LEN],
+            len: &mut usize,
+            chunk: &str,
+        ) -> Result<(), NameError> {
+            let chunk = chunk.as_bytes();
+            let new_len = *len + chunk.len();
+            if new_len > SALT_LEN {
+                return Err(NameError::TooLong);
+            }
+            if chunk.contains(&0) {
+                return Err(NameError::NullChar);
+            }
+            bytes[*len..new_len].copy_from_slice(chunk);
+            *len = new_len;
+            Ok(())
+        }
+
+        let mut bytes = [0; SALT_LEN];
+        let mut len = 0;
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                append(&mut bytes, &mut len, separator)?;
+            }
+            append(&mut bytes, &mut len, part)?;
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Returns the zero-padded byte representation of this name. This is the exact value
+    /// placed into the salt section of the Blake2b initialization block when deriving
+    /// a [named child](SecretTree::child()), so it can be used to reproduce derivation
+    /// independently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::Name;
+    /// let name = Name::new("foo");
+    /// assert_eq!(name.as_bytes(), b"foo\0\0\0\0\0\0\0\0\0\0\0\0\0");
+    /// ```
+    pub const fn as_bytes(&self) -> &[u8; MAX_NAME_LEN] {
+        &self.0
+    }
+}
+
+impl FromStr for Name {
+    type Err = NameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let byte_len = name.len();
+        if byte_len > SALT_LEN {
+            return Err(NameError::TooLong);
+        }
+        if name.as_bytes().contains(&0) {
+            return Err(NameError::NullChar);
+        }
+
+        let mut bytes = [0; SALT_LEN];
+        bytes[..byte_len].copy_from_slice(name.as_bytes());
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for Name {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        let str_len = self.0.iter().position(|&ch| ch == 0).unwrap_or(SALT_LEN);
+        let bytes = &self.0[..str_len];
+        debug_assert!(
+            str::from_utf8(bytes).is_ok(),
+            "`Name` invariant violated: bytes are not valid UTF-8"
+        );
+        unsafe {
+            // SAFETY: safe by construction; we only ever create `Name`s from valid UTF-8 sequences.
+            str::from_utf8_unchecked(bytes)
+        }
+    }
+}
+
+/// Compares the name with a string (without zero padding).
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_ref() == other
+    }
+}
+
+/// Compares the name with a string (without zero padding).
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_ref() == *other
+    }
+}
+
+/// Renders the name as a quoted string, escaping control chars and other non-printable chars
+/// in the same way as the `Debug` implementation for `str`.
+impl fmt::Debug for Name {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_tuple("Name").field(&self.as_ref()).finish()
+    }
+}
+
+/// Outputs the name as is. Names may contain arbitrary chars except for `\0` (including control
+/// chars); if names come from an untrusted source, consider logging them with the [`Debug`](fmt::Debug)
+/// implementation, which escapes non-printable chars.
+impl fmt::Display for Name {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_ref())
+    }
+}
+
+/// Errors that can occur when converting a `&str` into [`Name`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NameError {
+    /// The string is too long. `Name`s should be 0..=16 bytes.
+    TooLong,
+    /// Name contains a null char `\0`.
+    NullChar,
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::TooLong => "name is too long, 0..=16 bytes expected",
+            Self::NullChar => "name contains a null char",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NameError {}
+
+#[cfg(doctest)]
+doc_comment::doctest!("../README.md");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "rng")]
+    use rand::Rng;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn children_with_same_bytes_in_key() {
+        let name = Name::new("A");
+        let index = u64::from(b'A');
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let named_child = tree.child(name);
+        let indexed_child = tree.index(index);
+        assert_ne!(
+            named_child.seed.expose_secret(),
+            indexed_child.seed.expose_secret()
+        );
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn fill_and_rng_result_in_different_data() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = [0_u64; 8];
+        tree.child(Name::new("foo")).fill(&mut buffer);
+        let other_buffer: [u64; 8] = tree.child(Name::new("foo")).rng().gen();
+        assert_ne!(buffer, other_buffer);
+    }
+
+    #[test]
+    #[should_panic(expected = "supplied buffer (12 bytes) is too small to be filled")]
+    fn filling_undersized_key() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = [0_u8; 12];
+        tree.fill(&mut buffer);
+    }
+
+    #[test]
+    fn error_filling_undersized_key() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = [0_u8; 12];
+        let err = tree.try_fill(&mut buffer).unwrap_err();
+
+        assert!(matches!(
+            err,
+            FillError::BufferTooSmall {
+                size: 12,
+                min_supported_size: 16,
+            }
+        ));
+        assert!(err.is_too_small() && !err.is_too_large());
+        assert_eq!((err.size(), err.bound()), (12, 16));
+        let err = err.to_string();
+        assert!(
+            err.contains("supplied buffer (12 bytes) is too small to be filled"),
+            "{err}"
+        );
+        assert!(err.contains("min supported size is 16 bytes"), "{err}");
+    }
+
+    #[test]
+    #[should_panic(expected = "supplied buffer (80 bytes) is too large to be filled")]
+    fn filling_oversized_key() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = [0_u64; 10];
+        tree.fill(&mut buffer);
+    }
+
+    #[test]
+    fn error_filling_oversized_key() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = [0_u64; 10];
+        let err = tree.try_fill(&mut buffer).unwrap_err();
+
+        assert!(matches!(
+            err,
+            FillError::BufferTooLarge {
+                size: 80,
+                max_supported_size: 64,
+            }
+        ));
+        assert!(err.is_too_large() && !err.is_too_small());
+        assert_eq!((err.size(), err.bound()), (80, 64));
+        let err = err.to_string();
+        assert!(
+            err.contains("supplied buffer (80 bytes) is too large to be filled"),
+            "{err}"
+        );
+        assert!(err.contains("max supported size is 64 bytes"), "{err}");
+    }
+
+    #[test]
+    fn filling_acceptable_buffers() {
+        let mut u8_buffer = [0_u8; 40];
+        let mut i32_buffer = [0_i32; 16];
+        let mut u128_buffer = [0_u128];
+        // Using `Vec` to store secrets is usually a bad idea because of its placement in heap;
+        // here it is used just to test capabilities.
+        let mut vec_buffer = [0_u16; 24];
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        tree.child(Name::new("u8")).fill(&mut u8_buffer[..]);
+        tree.child(Name::new("i32")).fill(&mut i32_buffer);
+        tree.child(Name::new("u128")).fill(&mut u128_buffer);
+        tree.child(Name::new("vec")).fill(&mut vec_buffer[..]);
+    }
+
+    #[test]
+    fn named_children_are_equivalent_to_child_calls() {
+        const NAMES: &[Name] = &[Name::new("test"), Name::new("other"), Name::new("")];
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let children: Vec<_> = tree.named_children(NAMES).collect();
+        assert_eq!(children.len(), NAMES.len());
+        for ((name, child), expected_name) in children.into_iter().zip(NAMES) {
+            assert_eq!(name, *expected_name);
+            let expected_child = tree.child(name);
+            assert_eq!(
+                child.seed.expose_secret(),
+                expected_child.seed.expose_secret()
+            );
+        }
+    }
+
+    #[test]
+    fn indexed_children_are_equivalent_to_chained_calls() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let children: Vec<_> = tree.indexed_children(Name::new("test"), 5..10).collect();
+        assert_eq!(children.len(), 5);
+        let named_child = tree.child(Name::new("test"));
+        for ((i, child), expected_index) in children.into_iter().zip(5..) {
+            assert_eq!(i, expected_index);
+            assert_eq!(child, named_child.index(i));
+        }
+
+        let other_children: Vec<_> = named_child.indices(5..10).collect();
+        assert_eq!(other_children.len(), 5);
+        for (i, child) in other_children {
+            assert_eq!(child, named_child.index(i));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn selecting_children() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let children = tree.select(10, 4);
+        assert_eq!(children.len(), 4);
+        for (i, child) in (0..).zip(&children) {
+            assert_eq!(*child, tree.index(i));
+        }
+        assert!(tree.select(10, 0).is_empty());
+        assert_eq!(tree.select(3, 3).len(), 3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "Cannot select 4 children out of 3")]
+    fn selecting_too_many_children() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let _ = tree.select(3, 4);
+    }
+
+    #[test]
+    fn filling_small_buffers() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut small_buffer = [0_u8; 12];
+        tree.child(Name::new("small")).fill_small(&mut small_buffer);
+        assert_ne!(small_buffer, [0; 12]);
+
+        let mut buffer = [0_u8; 16];
+        tree.child(Name::new("small")).fill(&mut buffer);
+        assert_ne!(buffer[..12], small_buffer);
+
+        let mut tag = 0_u64;
+        tree.child(Name::new("tag")).fill_small(&mut tag);
+        assert_ne!(tag, 0);
+
+        let err = tree
+            .child(Name::new("small"))
+            .try_fill_small(&mut buffer)
+            .unwrap_err();
+        assert!(matches!(err, FillError::BufferTooLarge { size: 16, .. }));
+        let err = tree
+            .child(Name::new("small"))
+            .try_fill_small(&mut [0_u8; 0][..])
+            .unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { size: 0, .. }));
+    }
+
+    #[test]
+    fn derived_child_seeds_are_unique() {
+        const COUNT: u64 = 1_024;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut seeds = HashSet::new();
+        let mut insert_seeds = |children: &mut dyn Iterator<Item = SecretTree>| {
+            for child in children {
+                assert!(seeds.insert(*child.seed.expose_secret()));
+            }
+        };
+
+        insert_seeds(&mut (0..COUNT).map(|i| tree.index(i)));
+        insert_seeds(&mut (0..COUNT).map(|i| tree.child(format!("n{i}").parse().unwrap())));
+        // Salts for the following children coincide with salts for indexed children,
+        // so they must be distinguished by personalizations.
+        insert_seeds(&mut (0..COUNT).map(|i| tree.index2(i, 0)));
+        insert_seeds(&mut (0..COUNT).map(|i| tree.child_raw(&i.to_le_bytes()).unwrap()));
+        insert_seeds(&mut (0..COUNT).map(|i| {
+            let generation = u32::try_from(i + 1).unwrap();
+            tree.rotate(generation)
+        }));
+        assert_eq!(seeds.len(), 5 * 1_024);
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn labeled_rngs() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut rng = tree.child(Name::new("rng")).rng();
+        let mut labeled_rng = tree.child(Name::new("rng")).rng_labeled(0);
+        assert_eq!(rng.gen::<[u64; 4]>(), labeled_rng.gen::<[u64; 4]>());
+
+        let rng_outputs: HashSet<_> = (0..10)
+            .map(|label| {
+                tree.child(Name::new("rng"))
+                    .rng_labeled(label)
+                    .gen::<u128>()
+            })
+            .collect();
+        assert_eq!(rng_outputs.len(), 10);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn shuffling_slices() {
+        use rand::seq::SliceRandom;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut items: Vec<_> = (0_u32..100).collect();
+        tree.child(Name::new("shuffle")).shuffle(&mut items);
+        let mut expected_items: Vec<_> = (0_u32..100).collect();
+        expected_items.shuffle(&mut tree.child(Name::new("shuffle")).rng());
+        assert_eq!(items, expected_items);
+        assert_ne!(items, (0_u32..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn deriving_paths() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let digest = [0xab; 32];
+        let path = format!("foo/1/{}/bar", Segment::Digest(digest));
+        let child = tree.derive_path(&path).unwrap();
+        let expected_child = tree
+            .child(Name::new("foo"))
+            .index(1)
+            .digest(&digest)
+            .child(Name::new("bar"));
+        assert_eq!(child, expected_child);
+        assert_eq!(child.depth(), 4);
+        assert_eq!(tree.derive_path("").unwrap(), tree);
+
+        let err = tree.derive_path("foo/\0").unwrap_err();
+        assert!(matches!(err, PathError::InvalidName { position: 1, .. }));
+        let err = tree.with_max_depth(1).derive_path("foo/1").unwrap_err();
+        assert!(matches!(err, PathError::Depth(DepthError { max_depth: 1 })));
+    }
+
+    #[test]
+    fn validating_paths() {
+        SecretTree::validate_path("").unwrap();
+        SecretTree::validate_path("foo/1/bar").unwrap();
+        let err = SecretTree::validate_path("foo/").unwrap_err();
+        assert!(matches!(err, PathError::EmptySegment { position: 1 }));
+    }
+
+    #[test]
+    fn filling_floats() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut floats = [0.0_f64; 4];
+        tree.child(Name::new("floats")).fill(&mut floats);
+        let mut bits = [0_u64; 4];
+        tree.child(Name::new("floats")).fill(&mut bits);
+        assert_eq!(floats.map(f64::to_bits), bits);
+
+        let mut floats = [0.0_f32; 4];
+        tree.child(Name::new("floats")).fill(&mut floats);
+        let mut bits = [0_u32; 4];
+        tree.child(Name::new("floats")).fill(&mut bits);
+        assert_eq!(floats.map(f32::to_bits), bits);
+    }
+
+    #[test]
+    fn filling_indexed_elements() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut keys = [[0_u64; 4]; 5];
+        tree.fill_indexed(&mut keys);
+        for (i, child) in tree.indices(0..5) {
+            let mut expected_key = [0_u64; 4];
+            child.fill(&mut expected_key);
+            assert_eq!(keys[usize::try_from(i).unwrap()], expected_key);
+        }
+
+        let mut small_keys = [[0_u8; 8]; 4];
+        let err = tree.try_fill_indexed(&mut small_keys).unwrap_err();
+        assert_eq!((err.size(), err.bound()), (8, 16));
+        tree.fill_indexed::<[u8; 16]>(&mut []);
+    }
+
+    #[test]
+    fn filling_with_xof() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut output = [0_u8; 150];
+        tree.duplicate().fill_xof(&mut output);
+        let mut expected = [0_u8; 192];
+        for (i, block) in expected.chunks_mut(64).enumerate() {
+            derive_key(
+                block,
+                Index::Number(i as u64),
+                *b"xof\0\0\0\0\0",
+                tree.seed.expose_secret(),
+            );
+        }
+        assert_eq!(output, expected[..150]);
+
+        for len in [0, 1, 16, 63, 64, 65, 128] {
+            let mut prefix = vec![0_u8; len];
+            tree.duplicate().fill_xof(prefix.as_mut_slice());
+            assert_eq!(prefix, output[..len]);
+        }
+
+        let mut key = [0_u8; 32];
+        tree.duplicate().fill(&mut key);
+        assert_ne!(key, output[..32]);
+
+        let mut ints = [0_u64; 18];
+        tree.fill_xof(&mut ints);
+        for (int, chunk) in ints.iter().zip(output.chunks(8)) {
+            assert_eq!(*int, u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn filling_heapless_vecs() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut key = heapless::Vec::<u8, 64>::new();
+        key.resize(32, 0).unwrap();
+        tree.index(0).fill(&mut key);
+        let mut expected = [0_u8; 32];
+        tree.index(0).fill(&mut expected);
+        assert_eq!(key, expected);
+
+        let mut ints = heapless::Vec::<u64, 16>::new();
+        ints.resize(4, 0).unwrap();
+        tree.index(0).fill(&mut ints);
+        let mut expected = [0_u64; 4];
+        tree.index(0).fill(&mut expected);
+        assert_eq!(ints, expected);
+
+        let mut small_key = heapless::Vec::<u8, 64>::new();
+        small_key.resize(8, 0).unwrap();
+        let err = tree.index(0).try_fill(&mut small_key).unwrap_err();
+        assert_eq!((err.size(), err.bound()), (8, 16));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn fill_len_for_heapless_vec_is_not_runtime_len() {
+        type Key = heapless::Vec<u8, 64>;
+
+        assert_eq!(SecretTree::fill_len_for::<Key>(), mem::size_of::<Key>());
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut key = Key::new();
+        key.resize(32, 0).unwrap();
+        let written = tree.try_fill_counted(&mut key).unwrap();
+        assert_eq!(written, 32);
+        assert_ne!(SecretTree::fill_len_for::<Key>(), written);
+    }
+
+    #[test]
+    fn creating_multiple_secrets() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let secrets = tree.create_secrets::<[u64; 4], 5>();
+        for (i, secret) in (0..).zip(&secrets) {
+            let expected: SecretBox<[u64; 4]> = tree.index(i).create_secret();
+            assert_eq!(secret.expose_secret(), expected.expose_secret());
+        }
+
+        let [] = tree.create_secrets::<[u8; 32], 0>();
+        let err = tree.try_create_secrets::<[u8; 8], 3>().unwrap_err();
+        assert_eq!((err.size(), err.bound()), (8, 16));
+    }
+
+    #[test]
+    fn filling_nested_arrays() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut matrix = [[0_u8; 8]; 4];
+        tree.child(Name::new("matrix")).fill(&mut matrix);
+        let mut flat = [0_u8; 32];
+        tree.child(Name::new("matrix")).fill(&mut flat);
+        assert_eq!(matrix.concat(), flat);
+
+        let mut matrix = [[0_u32; 2]; 4];
+        tree.child(Name::new("matrix")).fill(&mut matrix);
+        let mut flat = [0_u32; 8];
+        tree.child(Name::new("matrix")).fill(&mut flat);
+        assert_eq!(matrix.concat(), flat);
+
+        let mut small_matrix = [[0_u8; 3]; 3];
+        let err = tree.index(0).try_fill(&mut small_matrix).unwrap_err();
+        assert_eq!((err.size(), err.bound()), (9, 16));
+        let mut large_matrix = [[0_u64; 3]; 3];
+        let err = tree.index(0).try_fill(&mut large_matrix).unwrap_err();
+        assert_eq!((err.size(), err.bound()), (72, 64));
+    }
+
+    #[test]
+    fn deriving_children_from_strings() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let child = tree.try_child_str(String::from("test")).unwrap();
+        assert_eq!(child, tree.child(Name::new("test")));
+
+        let err = tree.try_child_str("a\0").unwrap_err();
+        assert!(matches!(err, NameError::NullChar));
+        let err = tree.try_child_str("x".repeat(17)).unwrap_err();
+        assert!(matches!(err, NameError::TooLong));
+    }
+
+    #[test]
+    fn digest_chains() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let digests = [[1; 32], [2; 32], [3; 32]];
+        let descendant = tree.digest_chain(digests);
+        assert_eq!(descendant.depth(), 3);
+        assert_eq!(
+            descendant,
+            tree.digest(&digests[0])
+                .digest(&digests[1])
+                .digest(&digests[2])
+        );
+        assert_eq!(tree.digest_chain([]), tree);
+    }
+
+    #[test]
+    fn comparing_names_with_strings() {
+        let name = Name::new("accounts");
+        assert_eq!(name, "accounts");
+        assert_eq!(name, *"accounts");
+        assert_ne!(name, "account");
+        assert_ne!(name, "accounts\0");
+        assert_eq!(Name::new(""), "");
+        assert_eq!(Name::new("0123456789abcdef"), "0123456789abcdef");
+    }
+
+    #[test]
+    fn deriving_single_segments() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let segments = [
+            (
+                Segment::Name(Name::new("test")),
+                tree.child(Name::new("test")),
+            ),
+            (Segment::Index(5), tree.index(5)),
+            (Segment::Index2(5, 7), tree.index2(5, 7)),
+            (
+                Segment::Bytes(RawId::new(&[1, 2, 3]).unwrap()),
+                tree.child_raw(&[1, 2, 3]).unwrap(),
+            ),
+            (Segment::Digest([1; 32]), tree.digest(&[1; 32])),
+            (Segment::Binding(hash_to_salt(b"aad")), tree.bind(b"aad")),
+            (
+                Segment::Split(SplitHalf::Rng),
+                tree.split_half(SplitHalf::Rng),
+            ),
+            (Segment::Rotation(2), tree.rotate(2)),
+        ];
+        for (segment, expected) in segments {
+            assert_eq!(tree.derive_segment(segment), expected);
+            assert_eq!(
+                tree.derive_secret_seed(segment).expose_secret(),
+                expected.seed.expose_secret()
+            );
+        }
+
+        let bounded_tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123)).with_max_depth(0);
+        let err = bounded_tree
+            .try_derive_segment(Segment::Index(0))
+            .unwrap_err();
+        assert_eq!(err.max_depth, 0);
+    }
+
+    #[test]
+    fn two_dimensional_indices() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut expected_seed = [0_u8; 32];
+        let mut salt = [0_u8; 16];
+        salt[..8].copy_from_slice(&3_u64.to_le_bytes());
+        salt[8..].copy_from_slice(&5_u64.to_le_bytes());
+        derive_key(
+            &mut expected_seed,
+            Index::Bytes(salt),
+            *b"index2\0\0",
+            tree.seed.expose_secret(),
+        );
+        let child = tree.index2(3, 5);
+        assert_eq!(*child.seed.expose_secret(), expected_seed);
+        assert_eq!(child.depth(), 1);
+
+        assert_ne!(child, tree.index2(5, 3));
+        assert_ne!(child, tree.index(3).index(5));
+        assert_ne!(tree.index2(3, 0), tree.index(3));
+    }
+
+    #[test]
+    fn children_with_raw_bytes() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let child = tree.child_raw(b"ab").unwrap();
+        let mut expected_seed = [0_u8; 32];
+        derive_key(
+            &mut expected_seed,
+            Index::Bytes(*b"ab\0\0\0\0\0\0\0\0\0\0\0\0\0\x02"),
+            *b"raw\0\0\0\0\0",
+            tree.seed.expose_secret(),
+        );
+        assert_eq!(*child.seed.expose_secret(), expected_seed);
+        assert_ne!(child, tree.child_raw(b"ab\0").unwrap());
+        assert_ne!(child, tree.child(Name::new("ab")));
+        assert_ne!(tree.child_raw(&[]).unwrap(), tree.child_raw(&[0]).unwrap());
+        assert_ne!(tree.child_raw(&[]).unwrap(), tree.index(0));
+
+        let max_len_child = tree.child_raw(&[0xff; 15]).unwrap();
+        assert_eq!(max_len_child.depth(), 1);
+        let err = tree.child_raw(&[0xff; 16]).unwrap_err();
+        assert_eq!((err.actual, err.max), (16, 15));
+    }
+
+    #[test]
+    fn extracting_seeds() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let child = tree.child(Name::new("test"));
+        let cloned_seed = child.clone_seed();
+        assert_eq!(cloned_seed.expose_secret(), child.seed.expose_secret());
+        let seed = child.into_seed();
+        assert_eq!(seed.expose_secret(), cloned_seed.expose_secret());
+        assert_eq!(SecretTree::from_seed(seed), tree.child(Name::new("test")));
+
+        let seed = Seed::from(tree.child(Name::new("test")));
+        assert_eq!(seed.expose_secret(), cloned_seed.expose_secret());
+        let restored_tree: SecretTree = seed.into();
+        assert_eq!(restored_tree, tree.child(Name::new("test")));
+        assert_eq!(restored_tree.depth(), 0);
+    }
+
+    #[test]
+    fn binding_to_associated_data() {
+        use blake2::{
+            digest::{consts::U16, Digest},
+            Blake2b,
+        };
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let child = tree.bind(b"protocol v1");
+        let mut expected_seed = [0_u8; 32];
+        derive_key(
+            &mut expected_seed,
+            Index::Bytes(Blake2b::<U16>::digest(b"protocol v1").into()),
+            *b"bind\0\0\0\0",
+            tree.seed.expose_secret(),
+        );
+        assert_eq!(*child.seed.expose_secret(), expected_seed);
+        assert_eq!(child.depth(), 1);
+        assert_eq!(child, tree.bind(b"protocol v1"));
+        assert_ne!(child, tree.bind(b"protocol v2"));
+        assert_ne!(tree.bind(b""), tree.child_raw(b"").unwrap());
+    }
+
+    #[test]
+    fn deriving_keys_with_info() {
+        use blake2::{
+            digest::{consts::U16, Digest},
+            Blake2b,
+        };
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut key = [0_u8; 32];
+        tree.derive_info(b"test", &mut key);
+        let mut expected_key = [0_u8; 32];
+        derive_key(
+            &mut expected_key,
+            Index::Bytes(Blake2b::<U16>::digest(b"test").into()),
+            *b"info\0\0\0\0",
+            tree.seed.expose_secret(),
+        );
+        assert_eq!(key, expected_key);
+
+        let mut other_key = [0_u8; 32];
+        tree.derive_info(b"test\0", &mut other_key);
+        assert_ne!(other_key, key);
+        let long_info = [1_u8; 100];
+        tree.derive_info(&long_info, &mut other_key);
+        assert_ne!(other_key, key);
+
+        let err = tree.try_derive_info(b"test", &mut [0_u8; 8]).unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { size: 8, .. }));
+    }
+
+    #[test]
+    fn weak_seed_heuristic() {
+        let weak_seeds: [[u8; 32]; 5] = [
+            [0xff; 32],
+            core::array::from_fn(|i| [1, 2, 3][i % 3]),
+            core::array::from_fn(|i| u8::try_from(i).unwrap()),
+            core::array::from_fn(|i| 250_u8.wrapping_sub(3 * u8::try_from(i).unwrap())),
+            *b"not a random seed; used in tests",
+        ];
+        for seed in &weak_seeds {
+            let tree = SecretTree::from_slice(seed).unwrap();
+            assert!(tree.seed_looks_weak(), "{seed:?}");
+        }
+
+        let mut rng = ChaChaRng::seed_from_u64(123);
+        for _ in 0..100 {
+            assert!(!SecretTree::new(&mut rng).seed_looks_weak());
+        }
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn rng_with_seed_check() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut rng = tree.index(0).try_rng().unwrap();
+        let mut expected_rng = tree.index(0).rng();
+        assert_eq!(rng.next_u64(), expected_rng.next_u64());
+
+        let err = SecretTree::placeholder().try_rng().unwrap_err();
+        assert_eq!(err, SeedError::WeakSeed);
+    }
+
+    #[cfg(feature = "aead")]
+    #[test]
+    fn deriving_cipher_material() {
+        use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let (key, nonce) = tree.index(0).into_cipher_material::<ChaCha20Poly1305>();
+        let mut expected_key = [0_u8; 32];
+        tree.index(0)
+            .derive_raw(0, contexts::AEAD_KEY, &mut expected_key);
+        assert_eq!(key.as_slice(), expected_key);
+        assert_eq!(nonce.len(), 12);
+
+        let (other_key, other_nonce) = tree.index(1).into_cipher_material::<ChaCha20Poly1305>();
+        assert_ne!(key, other_key);
+        assert_ne!(nonce, other_nonce);
+
+        let (x_key, x_nonce) = tree.index(0).into_cipher_material::<XChaCha20Poly1305>();
+        assert_eq!(x_key, key);
+        assert_eq!(x_nonce.len(), 24);
+        assert_ne!(x_nonce[..12], nonce[..]);
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn comparing_seeds_in_constant_time() {
+        use subtle::ConstantTimeEq;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let seed = CtSeed::new(tree.index(0).seed().clone());
+        assert!(bool::from(seed.ct_eq(&tree.index(0).seed().clone().into())));
+        assert!(!bool::from(
+            seed.ct_eq(&tree.index(1).seed().clone().into())
+        ));
+        assert_eq!(
+            seed.into_inner().expose_secret(),
+            tree.index(0).seed().expose_secret()
+        );
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn constant_time_tree_selection() {
+        use subtle::Choice;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let a = tree.child(Name::new("a"));
+        let b = tree.index(1).index(2);
+        let selected = SecretTree::conditional_select(&a, &b, Choice::from(0));
+        assert_eq!(selected, a);
+        assert_eq!(selected.depth(), 0);
+        let selected = SecretTree::conditional_select(&a, &b, Choice::from(1));
+        assert_eq!(selected, b);
+        assert_eq!(selected.depth(), 0);
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn creating_rngs_of_custom_types() {
+        // RNG with an 8-byte seed, which is too short to be derived.
+        #[derive(Debug)]
+        struct SmallSeedRng;
+
+        impl RngCore for SmallSeedRng {
+            fn next_u32(&mut self) -> u32 {
+                unreachable!()
+            }
+            fn next_u64(&mut self) -> u64 {
+                unreachable!()
+            }
+            fn fill_bytes(&mut self, _dest: &mut [u8]) {
+                unreachable!()
+            }
+            fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                unreachable!()
+            }
+        }
+
+        impl SeedableRng for SmallSeedRng {
+            type Seed = [u8; 8];
+
+            fn from_seed(_seed: Self::Seed) -> Self {
+                Self
+            }
+        }
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut rng: ChaChaRng = tree.child(Name::new("rng")).rng_as();
+        let mut expected_rng = tree.child(Name::new("rng")).rng();
+        assert_eq!(rng.gen::<[u64; 4]>(), expected_rng.gen::<[u64; 4]>());
+
+        let err = tree.try_rng_as::<SmallSeedRng>().unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { size: 8, .. }));
+    }
+
+    #[test]
+    fn usize_indices() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        for i in [0_usize, 1, 1_000, usize::MAX] {
+            assert_eq!(tree.index_usize(i), tree.index(i as u64));
+        }
+    }
+
+    #[test]
+    fn splitting_tree() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let (key_tree, rng_tree) = tree.child(Name::new("split")).split();
+        assert_ne!(key_tree, rng_tree);
+        assert_eq!(key_tree.depth(), 2);
+        assert_eq!(rng_tree.depth(), 2);
+        assert_eq!(key_tree, tree.derive_path("split/!0").unwrap());
+        assert_eq!(rng_tree, tree.derive_path("split/!1").unwrap());
+
+        let mut expected_seed = [0_u8; 32];
+        tree.child(Name::new("split"))
+            .derive_raw(1, *b"split\0\0\0", &mut expected_seed);
+        assert_eq!(*rng_tree.seed().expose_secret(), expected_seed);
+    }
+
+    #[test]
+    fn rotating_tree() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123)).with_max_depth(1);
+        let rotated = tree.rotate(2);
+        assert_eq!(rotated.depth(), 1);
+        assert_eq!(rotated, tree.derive_path("@2").unwrap());
+        assert_ne!(rotated, tree.rotate(1));
+        assert_ne!(rotated, tree.index(2));
+        // Generation 0 is the tree itself, even if the maximum depth is reached.
+        assert_eq!(rotated.rotate(0), rotated);
+        assert_eq!(rotated.derive_path("@0").unwrap(), rotated);
+
+        let mut expected_seed = [0_u8; 32];
+        tree.derive_raw(2, *b"rotate\0\0", &mut expected_seed);
+        assert_eq!(*rotated.seed().expose_secret(), expected_seed);
+    }
+
+    #[test]
+    fn filling_with_post_processing() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut expected = [0_u16; 16];
+        tree.child(Name::new("key")).fill(&mut expected);
+        expected[0] |= 1;
+
+        let mut buffer = [0_u16; 16];
+        tree.child(Name::new("key"))
+            .fill_with(&mut buffer, |bytes| {
+                assert_eq!(bytes.len(), 32);
+                bytes[0] |= 1;
+            });
+        assert_eq!(buffer, expected);
+
+        let mut small_buffer = [0_u8; 8];
+        let err = tree
+            .child(Name::new("small"))
+            .try_fill_with(&mut small_buffer, |_| unreachable!())
+            .unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { size: 8, .. }));
+    }
+
+    #[test]
+    fn filling_many_buffers() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut u8_buffer = [0_u8; 40];
+        let mut i32_buffer = [0_i32; 16];
+        let mut u128_buffer = 0_u128;
+        tree.fill_many([
+            (0, &mut u8_buffer as &mut dyn AsByteSliceMut),
+            (5, &mut i32_buffer),
+            (u64::MAX, &mut u128_buffer),
+        ]);
+
+        let mut expected_u8_buffer = [0_u8; 40];
+        tree.index(0).fill(&mut expected_u8_buffer);
+        assert_eq!(u8_buffer, expected_u8_buffer);
+        let mut expected_i32_buffer = [0_i32; 16];
+        tree.index(5).fill(&mut expected_i32_buffer);
+        assert_eq!(i32_buffer, expected_i32_buffer);
+        let mut expected_u128_buffer = 0_u128;
+        tree.index(u64::MAX).fill(&mut expected_u128_buffer);
+        assert_eq!(u128_buffer, expected_u128_buffer);
+    }
+
+    #[test]
+    fn error_filling_many_buffers() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = [0_u8; 16];
+        let mut small_buffer = [0_u8; 8];
+        let err = tree
+            .try_fill_many([
+                (0, &mut buffer as &mut dyn AsByteSliceMut),
+                (1, &mut small_buffer),
+            ])
+            .unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { size: 8, .. }));
+        assert_ne!(buffer, [0; 16]);
     }
-}
 
-impl fmt::Display for Name {
-    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.write_str(self.as_ref())
+    #[test]
+    fn filling_zeroizing_buffers() {
+        use secrecy::zeroize::Zeroizing;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = Zeroizing::new([0_u32; 8]);
+        tree.child(Name::new("buffer")).fill(&mut buffer);
+        let mut expected_buffer = [0_u32; 8];
+        tree.child(Name::new("buffer")).fill(&mut expected_buffer);
+        assert_eq!(*buffer, expected_buffer);
     }
-}
 
-/// Errors that can occur when converting a `&str` into [`Name`].
-#[derive(Debug)]
-#[non_exhaustive]
-pub enum NameError {
-    /// The string is too long. `Name`s should be 0..=16 bytes.
-    TooLong,
-    /// Name contains a null char `\0`.
-    NullChar,
-}
+    fn assert_fill_bounds<T, const MIN: usize, const MAX: usize>()
+    where
+        T: Copy + Default,
+        [T]: AsByteSliceMut,
+    {
+        assert_eq!(SecretTree::fill_len_for::<[T; MIN]>(), 16);
+        assert_eq!(SecretTree::fill_len_for::<[T; MAX]>(), 64);
 
-impl fmt::Display for NameError {
-    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.write_str(match self {
-            Self::TooLong => "name is too long, 0..=16 bytes expected",
-            Self::NullChar => "name contains a null char",
-        })
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = vec![T::default(); MAX + 1];
+        tree.child(Name::new("min")).fill(&mut buffer[..MIN]);
+        tree.child(Name::new("max")).fill(&mut buffer[..MAX]);
+
+        let err = tree.index(0).try_fill(&mut buffer[..MIN - 1]).unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { .. }));
+        let err = tree.index(1).try_fill(&mut buffer[..]).unwrap_err();
+        assert!(matches!(err, FillError::BufferTooLarge { .. }));
     }
-}
 
-#[cfg(feature = "std")]
-impl std::error::Error for NameError {}
+    #[test]
+    fn buffer_length_is_measured_in_bytes() {
+        assert_fill_bounds::<u8, 16, 64>();
+        assert_fill_bounds::<i8, 16, 64>();
+        assert_fill_bounds::<u16, 8, 32>();
+        assert_fill_bounds::<i16, 8, 32>();
+        assert_fill_bounds::<u32, 4, 16>();
+        assert_fill_bounds::<i32, 4, 16>();
+        assert_fill_bounds::<u64, 2, 8>();
+        assert_fill_bounds::<i64, 2, 8>();
+        assert_fill_bounds::<u128, 1, 4>();
+        assert_fill_bounds::<i128, 1, 4>();
+    }
 
-#[cfg(doctest)]
-doc_comment::doctest!("../README.md");
+    #[test]
+    fn raw_derivation_is_compatible_with_libsodium() {
+        use const_decoder::Decoder::Hex;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Test vector from libsodium.
+        const EXPECTED: [u8; 32] =
+            Hex.decode(b"154b291f11196737f8b7f491e4ca11764e0227d34f94295408a869f007aa8618");
+
+        let mut master_key = [0_u8; SEED_LEN];
+        for (i, byte) in master_key.iter_mut().enumerate() {
+            *byte = u8::try_from(i).unwrap();
+        }
+        let tree = SecretTree::from_slice(&master_key).unwrap();
+        let mut output = [0_u8; 32];
+        tree.derive_raw(32, *b"KDF test", &mut output);
+        assert_eq!(output, EXPECTED);
 
-    use rand::{Rng, SeedableRng};
+        let mut fill_output = [0_u8; 32];
+        tree.derive_raw(0, contexts::FILL_BYTES, &mut output);
+        tree.fill(&mut fill_output);
+        assert_eq!(output, fill_output);
+    }
 
+    #[cfg(all(feature = "alloc", feature = "rng"))]
     #[test]
-    fn children_with_same_bytes_in_key() {
-        let name = Name::new("A");
-        let index = u64::from(b'A');
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        let named_child = tree.child(name);
-        let indexed_child = tree.index(index);
-        assert_ne!(
-            named_child.seed.expose_secret(),
-            indexed_child.seed.expose_secret()
-        );
+    fn boxed_rng_is_equivalent_to_concrete_one() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let mut rng = tree.duplicate().rng();
+        let mut boxed_rng = tree.rng_boxed();
+        for _ in 0..10 {
+            assert_eq!(boxed_rng.next_u64(), rng.next_u64());
+        }
     }
 
     #[test]
-    fn fill_and_rng_result_in_different_data() {
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        let mut buffer = [0_u64; 8];
-        tree.child(Name::new("foo")).fill(&mut buffer);
-        let other_buffer: [u64; 8] = tree.child(Name::new("foo")).rng().gen();
-        assert_ne!(buffer, other_buffer);
+    fn filling_tuple() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let mut first = [0_u8; 16];
+        let mut second = 0_i128;
+        let mut third = [0_u32; 16];
+        tree.fill_tuple((&mut first, &mut second, &mut third));
+
+        let mut expected_first = [0_u8; 16];
+        let mut expected_second = 0_i128;
+        let mut expected_third = [0_u32; 16];
+        tree.index(0).fill(&mut expected_first);
+        tree.index(1).fill(&mut expected_second);
+        tree.index(2).fill(&mut expected_third);
+        assert_eq!(first, expected_first);
+        assert_eq!(second, expected_second);
+        assert_eq!(third, expected_third);
+
+        let mut small_buffer = 0_u64;
+        let err = tree
+            .try_fill_tuple((&mut first, &mut small_buffer))
+            .unwrap_err();
+        assert_eq!(err.size(), 8);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
-    #[should_panic(expected = "supplied buffer (12 bytes) is too small to be filled")]
-    fn filling_undersized_key() {
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        let mut buffer = [0_u8; 12];
-        tree.fill(&mut buffer);
+    fn creating_bytes_with_dynamic_length() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        for len in [16, 33, 64] {
+            let bytes = tree.duplicate().create_bytes(len);
+            let mut expected = alloc::vec![0_u8; len];
+            tree.duplicate().fill(expected.as_mut_slice());
+            assert_eq!(*bytes.expose_secret(), *expected);
+        }
+
+        let err = tree.duplicate().try_create_bytes(15).unwrap_err();
+        assert!(err.is_too_small());
+        let err = tree.try_create_bytes(usize::MAX).unwrap_err();
+        assert!(err.is_too_large());
     }
 
     #[test]
-    fn error_filling_undersized_key() {
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        let mut buffer = [0_u8; 12];
-        let err = tree.try_fill(&mut buffer).unwrap_err();
+    fn xor_filling() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let mut key = [0_u32; 8];
+        tree.duplicate().fill(&mut key);
 
-        assert!(matches!(
-            err,
-            FillError::BufferTooSmall {
-                size: 12,
-                min_supported_size: 16,
-            }
-        ));
-        let err = err.to_string();
-        assert!(
-            err.contains("supplied buffer (12 bytes) is too small to be filled"),
-            "{err}"
-        );
-        assert!(err.contains("min supported size is 16 bytes"), "{err}");
+        let mut buffer = [0x_1234_5678_u32; 8];
+        tree.duplicate().xor_fill(&mut buffer);
+        for (&actual, &key_element) in buffer.iter().zip(&key) {
+            assert_eq!(actual, key_element ^ 0x_1234_5678);
+        }
+
+        let mut small_buffer = [1_u8; 15];
+        let err = tree.try_xor_fill(&mut small_buffer).unwrap_err();
+        assert!(err.is_too_small());
+        assert_eq!(small_buffer, [1; 15]);
     }
 
+    #[cfg(feature = "ed25519")]
     #[test]
-    #[should_panic(expected = "supplied buffer (80 bytes) is too large to be filled")]
-    fn filling_oversized_key() {
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        let mut buffer = [0_u64; 10];
-        tree.fill(&mut buffer);
+    fn deriving_ed25519_keypair() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let secret_key = tree.duplicate().create_secret::<[u8; 32]>();
+        let (signing_key, verifying_key) = tree.into_ed25519_keypair();
+        assert_eq!(signing_key.to_bytes(), *secret_key.expose_secret());
+        assert_eq!(verifying_key, signing_key.verifying_key());
     }
 
+    #[cfg(feature = "password")]
     #[test]
-    fn error_filling_oversized_key() {
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        let mut buffer = [0_u64; 10];
-        let err = tree.try_fill(&mut buffer).unwrap_err();
+    fn creating_tree_from_password() {
+        let tree = SecretTree::from_password(b"password", b"somesalt");
+        let params = argon2::Params::new(19 * 1_024, 2, 1, Some(32)).unwrap();
+        let argon2 =
+            argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+        let mut expected_seed = [0_u8; 32];
+        argon2
+            .hash_password_into(b"password", b"somesalt", &mut expected_seed)
+            .unwrap();
+        assert_eq!(*tree.seed.expose_secret(), expected_seed);
+        assert_eq!(tree.depth(), 0);
 
-        assert!(matches!(
-            err,
-            FillError::BufferTooLarge {
-                size: 80,
-                max_supported_size: 64,
-            }
-        ));
-        let err = err.to_string();
-        assert!(
-            err.contains("supplied buffer (80 bytes) is too large to be filled"),
-            "{err}"
-        );
-        assert!(err.contains("max supported size is 64 bytes"), "{err}");
+        let err = SecretTree::try_from_password(b"password", b"salt").unwrap_err();
+        assert_eq!((err.actual, err.min, err.max), (4, 8, 64));
     }
 
+    #[cfg(feature = "uuid")]
     #[test]
-    fn filling_acceptable_buffers() {
-        let mut u8_buffer = [0_u8; 40];
-        let mut i32_buffer = [0_i32; 16];
-        let mut u128_buffer = [0_u128];
-        // Using `Vec` to store secrets is usually a bad idea because of its placement in heap;
-        // here it is used just to test capabilities.
-        let mut vec_buffer = [0_u16; 24];
+    fn deriving_uuids() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let id = tree.derive_uuid(5);
+        assert_eq!(id.get_version(), Some(uuid::Version::Random));
+        assert_eq!(id.get_variant(), uuid::Variant::RFC4122);
 
-        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
-        tree.child(Name::new("u8")).fill(&mut u8_buffer[..]);
-        tree.child(Name::new("i32")).fill(&mut i32_buffer);
-        tree.child(Name::new("u128")).fill(&mut u128_buffer);
-        tree.child(Name::new("vec")).fill(&mut vec_buffer[..]);
+        let mut bytes = [0_u8; 16];
+        tree.index(5).fill(&mut bytes);
+        let id_bytes = id.as_bytes();
+        assert_eq!(id_bytes[..6], bytes[..6]);
+        assert_eq!(id_bytes[6] & 0x0f, bytes[6] & 0x0f);
+        assert_eq!(id_bytes[7], bytes[7]);
+        assert_eq!(id_bytes[8] & 0x3f, bytes[8] & 0x3f);
+        assert_eq!(id_bytes[9..], bytes[9..]);
+        assert_eq!(tree.derive_uuid(5), id);
+    }
+
+    #[test]
+    fn filling_with_byte_count() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let mut buffer = [0_u32; 6];
+        assert_eq!(tree.duplicate().try_fill_counted(&mut buffer).unwrap(), 24);
+        let mut expected = [0_u32; 6];
+        tree.duplicate().fill(&mut expected);
+        assert_eq!(buffer, expected);
+
+        let mut small_buffer = [0_u16; 4];
+        let err = tree.try_fill_counted(&mut small_buffer).unwrap_err();
+        assert_eq!(err.size(), 8);
     }
 
     #[test]
@@ -730,6 +4498,57 @@ mod tests {
         assert!(matches!(err, NameError::TooLong));
     }
 
+    #[test]
+    fn name_concatenation() {
+        assert_eq!(Name::try_concat(&[]).unwrap(), Name::new(""));
+        assert_eq!(
+            Name::try_concat(&["ab", "", "cd"]).unwrap(),
+            Name::new("abcd")
+        );
+        assert_eq!(Name::try_join(&["ab"], "::").unwrap(), Name::new("ab"));
+        assert_eq!(
+            Name::try_join(&["a", "b", "c"], "::").unwrap(),
+            Name::new("a::b::c")
+        );
+        assert_eq!(
+            Name::try_join(&["Overly", "long", "name"], " ").unwrap(),
+            Name::new("Overly long name")
+        );
+
+        let err = Name::try_join(&["Overly", "long", "name?"], " ").unwrap_err();
+        assert!(matches!(err, NameError::TooLong));
+        let err = Name::try_concat(&["some", "\0name"]).unwrap_err();
+        assert!(matches!(err, NameError::NullChar));
+        let err = Name::try_join(&["some", "name"], "\0").unwrap_err();
+        assert!(matches!(err, NameError::NullChar));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "`Name` invariant violated")]
+    fn name_with_invalid_utf8_is_caught_in_debug_builds() {
+        let name = Name([0xff; MAX_NAME_LEN]);
+        let _ = name.as_ref();
+    }
+
+    #[test]
+    fn name_from_array() {
+        assert_eq!(Name::from_array(&[]), Name::new(""));
+        assert_eq!(Name::from_array(b"test"), Name::new("test"));
+        let utf8_bytes: &[u8; 8] = "тест".as_bytes().try_into().unwrap();
+        assert_eq!(Name::from_array(utf8_bytes), Name::new("тест"));
+        assert_eq!(
+            Name::from_array(b"Overly long name"),
+            Name::new("Overly long name")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "name is not valid UTF-8")]
+    fn name_from_invalid_utf8_array() {
+        let _name = Name::from_array(&[b'a', 0xff]);
+    }
+
     #[test]
     fn name_new_pads_input_with_zeros() {
         const SAMPLES: &[(Name, &[u8; MAX_NAME_LEN])] = &[
@@ -761,6 +4580,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn name_debug_escapes_control_chars() {
+        let name = Name::new("a\x1b[31m\r\nb\u{7f}");
+        assert_eq!(format!("{name:?}"), r#"Name("a\u{1b}[31m\r\nb\u{7f}")"#);
+        assert_eq!(name.to_string(), "a\x1b[31m\r\nb\u{7f}");
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn constant_time_name_comparison() {
+        use subtle::ConstantTimeEq;
+
+        let name = Name::new("foo");
+        assert!(bool::from(name.ct_eq(&Name::new("foo"))));
+        assert!(!bool::from(name.ct_eq(&Name::new("fo"))));
+        assert!(!bool::from(name.ct_eq(&Name::new("foo\u{1}"))));
+        assert!(!bool::from(name.ct_eq(&Name::new("bar"))));
+    }
+
     #[test]
     fn buffers_with_different_size_should_be_unrelated() {
         let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
@@ -771,6 +4609,304 @@ mod tests {
         assert!(bytes.iter().zip(&other_bytes).any(|(&x, &y)| x != y));
     }
 
+    #[test]
+    fn zeroizing_seed() {
+        let mut tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        assert!(!tree.is_zeroed());
+        let child = tree.index(0);
+        tree.seed.zeroize();
+        assert!(tree.is_zeroed());
+        assert!(!child.is_zeroed());
+
+        let mut seed = child.seed().clone();
+        seed.zeroize();
+        assert!(seed.is_all_zeros());
+        assert!(!child.is_zeroed());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn creating_tree_from_vec() {
+        let seed_bytes: Vec<u8> = (1..=32).collect();
+        let expected_tree = SecretTree::from_slice(&seed_bytes).unwrap();
+        let tree = SecretTree::try_from(seed_bytes).unwrap();
+        assert_eq!(tree, expected_tree);
+
+        let err = SecretTree::try_from(vec![1_u8; 16]).unwrap_err();
+        assert_eq!(err.actual, 16);
+        assert_eq!(
+            err.to_string(),
+            "unsupported byte length 16; expected 32 bytes"
+        );
+    }
+
+    #[test]
+    fn copying_seed_bytes() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let seed_bytes = tree.seed_bytes();
+        assert_eq!(*seed_bytes, *tree.seed().expose_secret());
+        assert_eq!(SecretTree::from_slice(&*seed_bytes).unwrap(), tree);
+    }
+
+    #[test]
+    fn placeholder_tree() {
+        let tree = SecretTree::placeholder();
+        assert!(tree.is_zeroed());
+        assert_eq!(tree.depth(), 0);
+    }
+
+    #[test]
+    fn placeholder_tree_can_be_formatted_and_hashed() {
+        let tree = SecretTree::placeholder();
+        assert_eq!(tree.to_string(), format!("SecretTree({})", tree.id()));
+
+        let mut trees = HashSet::new();
+        trees.insert(SecretTree::placeholder());
+        assert!(trees.contains(&tree));
+        assert!(!trees.contains(&SecretTree::new(&mut ChaChaRng::seed_from_u64(123))));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "placeholder `SecretTree`")]
+    fn derivation_from_placeholder_tree_panics() {
+        let _ = SecretTree::placeholder().child(Name::new("test"));
+    }
+
+    #[test]
+    fn creating_tree_from_seed_ref() {
+        let seed = Seed::new(&mut ChaChaRng::seed_from_u64(123));
+        let tree = SecretTree::from_seed_ref(&seed);
+        let other_tree = SecretTree::from_seed_ref(&seed);
+        assert_eq!(tree, other_tree);
+        assert_eq!(tree.seed().expose_secret(), seed.expose_secret());
+    }
+
+    #[test]
+    fn all_zero_seed_is_rejected() {
+        let err = SecretTree::from_seed_checked(Seed::from(&[0; 32])).unwrap_err();
+        assert_eq!(err, SeedError::WeakSeed);
+        assert_eq!(err.to_string(), "seed consists of zero bytes only");
+
+        let mut seed_bytes = [0; 32];
+        seed_bytes[31] = 1;
+        let _ = SecretTree::from_seed_checked(Seed::from(&seed_bytes)).unwrap();
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "all-zero seed supplied to `SecretTree`")]
+    fn all_zero_seed_panics_in_debug_builds() {
+        let _ = SecretTree::from_slice(&[0; 32]);
+    }
+
+    #[test]
+    fn trees_as_hash_map_keys() {
+        use std::collections::HashMap;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut cache = HashMap::new();
+        for i in 0..10 {
+            cache.insert(tree.index(i), i);
+        }
+        assert_eq!(cache.len(), 10);
+        assert_eq!(cache[&tree.index(5)], 5);
+        assert_eq!(cache.get(&tree.index(10)), None);
+        assert_eq!(cache.get(&tree.child(Name::new("5"))), None);
+
+        let restored_tree = SecretTree::from_seed(tree.index(3).seed.clone());
+        assert_eq!(cache[&restored_tree], 3);
+    }
+
+    #[test]
+    fn tree_ids() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let id = tree.id();
+        assert_ne!(id.as_bytes(), &tree.seed.expose_secret()[..16]);
+        assert_ne!(id.as_bytes(), &tree.seed.expose_secret()[16..]);
+        assert_eq!(SecretTree::from_seed(tree.seed.clone()).id(), id);
+
+        let children_ids: HashSet<_> = (0..10).map(|i| tree.index(i).id()).collect();
+        assert_eq!(children_ids.len(), 10);
+        assert!(!children_ids.contains(&id));
+
+        let tree_str = tree.to_string();
+        assert_eq!(tree_str, format!("SecretTree({id})"));
+        assert!(!tree_str.contains(&hex::encode(tree.seed.expose_secret())));
+
+        assert!(tree.verify_id(&id));
+        assert!(!tree.index(0).verify_id(&id));
+        let mut tweaked_bytes = *id.as_bytes();
+        tweaked_bytes[15] ^= 1;
+        assert!(!tree.verify_id(&TreeId::from_bytes(tweaked_bytes)));
+    }
+
+    #[test]
+    fn creating_tree_from_dyn_rng() {
+        let mut rng: Box<dyn CryptoRngCore> = Box::new(ChaChaRng::seed_from_u64(123));
+        let tree = SecretTree::from_dyn_rng(&mut *rng);
+        let expected_tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        assert_eq!(
+            tree.seed.expose_secret(),
+            expected_tree.seed.expose_secret()
+        );
+    }
+
+    #[test]
+    fn depth_is_tracked_for_children() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        assert_eq!(tree.depth(), 0);
+        let child = tree.child(Name::new("foo"));
+        assert_eq!(child.depth(), 1);
+        let grandchild = child.index(1).digest(&[0; 32]);
+        assert_eq!(grandchild.depth(), 3);
+        assert_eq!(SecretTree::from_seed(grandchild.seed.clone()).depth(), 0);
+    }
+
+    #[test]
+    fn max_depth_is_enforced() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let child = tree.child(Name::new("foo")).with_max_depth(2);
+        let grandchild = child.try_index(1).unwrap().try_digest(&[0; 32]).unwrap();
+        assert_eq!(grandchild.depth(), 3);
+
+        let err = grandchild.try_child(Name::new("bar")).unwrap_err();
+        assert_eq!(err, DepthError { max_depth: 3 });
+        assert_eq!(err.to_string(), "maximum derivation depth (3) exceeded");
+        assert!(grandchild.try_index(0).is_err());
+        assert!(grandchild.try_digest(&[0; 32]).is_err());
+
+        // The limit does not influence derivation.
+        let plain_grandchild = tree.child(Name::new("foo")).index(1).digest(&[0; 32]);
+        assert_eq!(
+            grandchild.seed.expose_secret(),
+            plain_grandchild.seed.expose_secret()
+        );
+    }
+
+    #[test]
+    fn child_cannot_raise_max_depth() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123)).with_max_depth(1);
+        let child = tree.index(0).with_max_depth(usize::MAX);
+        let err = child.try_index(0).unwrap_err();
+        assert_eq!(err, DepthError { max_depth: 1 });
+
+        // The limit can still be tightened.
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123)).with_max_depth(5);
+        let child = tree.index(0).with_max_depth(1);
+        let grandchild = child.try_index(0).unwrap();
+        assert_eq!(
+            grandchild.try_index(0).unwrap_err(),
+            DepthError { max_depth: 2 }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "maximum derivation depth (0) exceeded")]
+    fn exceeding_max_depth_with_infallible_method() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123)).with_max_depth(0);
+        let _ = tree.index(0);
+    }
+
+    #[cfg(feature = "track-path")]
+    #[test]
+    fn path_is_tracked_for_children() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        assert!(tree.path().is_empty());
+        let grandchild = tree.child(Name::new("foo")).index(1).digest(&[1; 32]);
+        assert_eq!(
+            grandchild.path(),
+            [
+                RecordedSegment::Segment(Segment::Name(Name::new("foo"))),
+                RecordedSegment::Segment(Segment::Index(1)),
+                RecordedSegment::RedactedDigest,
+            ]
+        );
+        assert_eq!(grandchild.path()[2].to_string(), "<redacted digest>");
+        let path = grandchild.path().iter().map(RecordedSegment::to_string);
+        let path = path.collect::<Vec<_>>().join("/");
+        assert!(SecretTree::validate_path(&path).is_err());
+
+        let plain_grandchild = SecretTree::new(&mut ChaChaRng::seed_from_u64(123))
+            .child(Name::new("foo"))
+            .index(1)
+            .digest(&[1; 32]);
+        assert_eq!(
+            grandchild.seed.expose_secret(),
+            plain_grandchild.seed.expose_secret()
+        );
+    }
+
+    #[test]
+    fn stretching_short_seeds() {
+        let mut seeds = vec![];
+        for len in 1..=SEED_LEN {
+            let bytes = vec![1_u8; len];
+            let tree = SecretTree::from_short_seed(&bytes).unwrap();
+            let same_tree = SecretTree::from_short_seed(&bytes).unwrap();
+            assert_eq!(tree.seed.expose_secret(), same_tree.seed.expose_secret());
+            seeds.push(*tree.seed.expose_secret());
+        }
+        // Inputs of different lengths must not collide.
+        for (i, seed) in seeds.iter().enumerate() {
+            assert!(seeds[i + 1..].iter().all(|other| other != seed));
+        }
+
+        let full_seed = SecretTree::from_slice(&[1; SEED_LEN]).unwrap();
+        assert_ne!(full_seed.seed.expose_secret(), &seeds[SEED_LEN - 1]);
+    }
+
+    #[test]
+    fn stretching_seed_with_invalid_length() {
+        let err = SecretTree::from_short_seed(&[]).unwrap_err();
+        assert_eq!(err.actual, 0);
+        let err = SecretTree::from_short_seed(&[0; 33]).unwrap_err();
+        assert_eq!(
+            err,
+            LenError {
+                actual: 33,
+                min: 1,
+                max: 32
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "unsupported byte length 33; expected 1..=32 bytes"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reading_and_writing_seed() {
+        use std::io;
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut buffer = vec![];
+        tree.to_writer(&mut buffer).unwrap();
+        assert_eq!(buffer, tree.seed.expose_secret());
+        let restored_tree = SecretTree::from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(restored_tree, tree);
+
+        let err = SecretTree::from_reader(&buffer[..31]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        let err = SecretTree::from_reader([0_u8; 32].as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(all(feature = "std", feature = "rng"))]
+    #[test]
+    fn writing_to_sink_matches_rng_output() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let child = tree.child(Name::new("pad"));
+        for len in [0, 1, 63, 64, 65, 1_000] {
+            let mut written = vec![];
+            child.write_to(&mut written, len).unwrap();
+            let mut expected = vec![0_u8; len];
+            tree.child(Name::new("pad")).rng().fill_bytes(&mut expected);
+            assert_eq!(written, expected);
+        }
+    }
+
     #[test]
     fn digest_derivation_depends_on_all_bits_of_digest() {
         const RNG_SEED: u64 = 12345;
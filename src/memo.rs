@@ -0,0 +1,108 @@
+//! Trees memoizing their named children.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{Name, SecretTree};
+
+/// [`SecretTree`] wrapper caching derived named children. This trades memory for speed
+/// in long-lived services that repeatedly access the same few named subtrees.
+///
+/// Since derivation is deterministic, [`Self::child()`] returns the same children
+/// as [`SecretTree::child()`]. Cached seeds are zeroized when the wrapper is dropped
+/// or [cleared](Self::clear()), as with standalone trees.
+///
+/// The cache is guarded by a mutex, so the wrapper can be shared among threads.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{MemoizedTree, Name, SecretTree};
+/// # use rand::thread_rng;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let memoized = MemoizedTree::new(tree);
+/// let signing = memoized.child(Name::new("signing")); // derived
+/// let signing_again = memoized.child(Name::new("signing")); // cached
+/// assert_eq!(signing, signing_again);
+/// assert_eq!(memoized.cached_len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct MemoizedTree {
+    inner: SecretTree,
+    children: Mutex<HashMap<Name, SecretTree>>,
+}
+
+impl MemoizedTree {
+    /// Wraps the provided tree. The cache is initially empty.
+    pub fn new(tree: SecretTree) -> Self {
+        Self {
+            inner: tree,
+            children: Mutex::default(),
+        }
+    }
+
+    fn lock_children(&self) -> MutexGuard<'_, HashMap<Name, SecretTree>> {
+        // The map is never left in an inconsistent state, so it's safe to ignore poisoning.
+        self.children.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns a reference to the wrapped tree.
+    pub fn as_tree(&self) -> &SecretTree {
+        &self.inner
+    }
+
+    /// Returns the number of cached children.
+    pub fn cached_len(&self) -> usize {
+        self.lock_children().len()
+    }
+
+    /// Removes all cached children, zeroizing their seeds.
+    pub fn clear(&self) {
+        self.lock_children().clear();
+    }
+
+    /// Unwraps the underlying tree, zeroizing seeds of cached children.
+    pub fn into_inner(self) -> SecretTree {
+        self.inner
+    }
+
+    /// Produces a child with the specified name. The child is derived on the first call
+    /// with the given name and is cached; subsequent calls return a copy of the cached child.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](SecretTree::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn child(&self, name: Name) -> SecretTree {
+        let mut children = self.lock_children();
+        children
+            .entry(name)
+            .or_insert_with(|| self.inner.child(name))
+            .duplicate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memoized_children() {
+        let tree = SecretTree::from_slice(&[7; 32]).unwrap();
+        let memoized = MemoizedTree::new(SecretTree::from_slice(&[7; 32]).unwrap());
+        for _ in 0..2 {
+            for name in [Name::new("a"), Name::new("b")] {
+                assert_eq!(memoized.child(name), tree.child(name));
+            }
+        }
+        assert_eq!(memoized.cached_len(), 2);
+
+        memoized.clear();
+        assert_eq!(memoized.cached_len(), 0);
+        assert_eq!(memoized.child(Name::new("a")), tree.child(Name::new("a")));
+        assert_eq!(memoized.into_inner(), tree);
+    }
+}
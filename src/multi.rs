@@ -0,0 +1,119 @@
+//! Batch derivation of raw keys.
+
+use core::fmt;
+
+use crate::{
+    kdf::{try_derive_key, Index, SALT_LEN},
+    FillError, SecretTree, CONTEXT_LEN, SEED_LEN,
+};
+
+/// Derives multiple raw keys from the seed of a [`SecretTree`] with arbitrary contexts
+/// and salts. Created with [`SecretTree::multi_deriver()`].
+///
+/// Each derived key is equivalent to a keyed Blake2b output with the tree seed as the key
+/// and the specified salt and personalization (context), i.e., the same procedure the crate
+/// uses internally (see the [crate docs](crate) for details). If the salt is
+/// a little-endian `u64` zero-padded to 16 bytes, the derivation is equivalent
+/// to [`SecretTree::try_derive_raw()`] and thus to libsodium's `crypto_kdf_derive_from_key`.
+///
+/// Note that keys do not share a precomputed keyed Blake2b state; both the salt and context
+/// are parts of the Blake2b initialization block, so each key costs a full derivation,
+/// exactly as much as a [`SecretTree::try_derive_raw()`] call. The deriver is thus
+/// a convenience over a sequence of such calls that additionally allows custom salts,
+/// rather than a performance optimization.
+///
+/// # Security
+///
+/// Contexts used by the crate internally (see [`contexts::ALL`](crate::contexts::ALL)) must not be used
+/// with the deriver. Otherwise, derived keys may coincide with the seeds of child trees
+/// or other secrets derived by the crate.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::SecretTree;
+/// # use rand::thread_rng;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let mut encryption_key = [0_u8; 32];
+/// let mut mac_key = [0_u8; 64];
+/// tree.multi_deriver()
+///     .push(*b"protocol", [1; 16], &mut encryption_key)?
+///     .push(*b"protocol", [2; 16], &mut mac_key)?;
+/// # Ok::<_, secret_tree::FillError>(())
+/// ```
+pub struct MultiDeriver<'a> {
+    seed: &'a [u8; SEED_LEN],
+}
+
+impl fmt::Debug for MultiDeriver<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MultiDeriver")
+            .finish_non_exhaustive()
+    }
+}
+
+impl MultiDeriver<'_> {
+    /// Derives a key with the specified context and salt into `output`. The key is derived
+    /// immediately, with a full derivation per call.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `output` does not have length `16..=64` bytes.
+    pub fn push(
+        &mut self,
+        context: [u8; CONTEXT_LEN],
+        salt: [u8; SALT_LEN],
+        output: &mut [u8],
+    ) -> Result<&mut Self, FillError> {
+        try_derive_key(output, Index::Bytes(salt), context, self.seed)?;
+        Ok(self)
+    }
+}
+
+impl SecretTree {
+    /// Creates a deriver of raw keys from the seed of this tree.
+    /// See [`MultiDeriver`] docs for details.
+    pub fn multi_deriver(&self) -> MultiDeriver<'_> {
+        MultiDeriver {
+            seed: self.derivation_seed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use super::*;
+
+    #[test]
+    fn multi_deriver_is_equivalent_to_individual_derivations() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let mut first_key = [0_u8; 32];
+        let mut second_key = [0_u8; 16];
+        let mut salt = [0_u8; SALT_LEN];
+        salt[..8].copy_from_slice(&42_u64.to_le_bytes());
+        tree.multi_deriver()
+            .push(*b"test\0\0\0\0", salt, &mut first_key)
+            .unwrap()
+            .push(*b"bytes\0\0\0", [0; SALT_LEN], &mut second_key)
+            .unwrap();
+
+        let mut expected_key = [0_u8; 32];
+        tree.derive_raw(42, *b"test\0\0\0\0", &mut expected_key);
+        assert_eq!(first_key, expected_key);
+        let mut expected_key = [0_u8; 16];
+        SecretTree::from_slice(&*tree.seed_bytes())
+            .unwrap()
+            .fill(&mut expected_key);
+        assert_eq!(second_key, expected_key);
+
+        let err = tree
+            .multi_deriver()
+            .push(*b"test\0\0\0\0", salt, &mut [0_u8; 8])
+            .unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { size: 8, .. }));
+    }
+}
@@ -0,0 +1,607 @@
+//! Derivation paths.
+
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+use core::{fmt, num::ParseIntError};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::SecretTree;
+use crate::{kdf::SALT_LEN, DepthError, LenError, Name, NameError};
+
+/// Single step of deriving a child [`SecretTree`](crate::SecretTree) from its parent.
+///
+/// Segments are non-secret metadata; they describe *how* a child was derived, but do not
+/// reveal anything about the seeds involved. The [`Display`](fmt::Display) implementation
+/// renders a segment in the same way it would appear in a `/`-separated path, with digests
+/// rendered in hex:
+///
+/// ```
+/// # use secret_tree::{Name, Segment};
+/// assert_eq!(Segment::Name(Name::new("foo")).to_string(), "foo");
+/// assert_eq!(Segment::Index(42).to_string(), "42");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Segment {
+    /// Child derived with [`SecretTree::child()`](crate::SecretTree::child()).
+    Name(Name),
+    /// Child derived with [`SecretTree::child_raw()`](crate::SecretTree::child_raw()).
+    /// Rendered as `#` followed by hex-encoded identifier bytes, e.g., `#dead`.
+    Bytes(RawId),
+    /// Child derived with [`SecretTree::index()`](crate::SecretTree::index()).
+    Index(u64),
+    /// Child derived with [`SecretTree::index2()`](crate::SecretTree::index2()).
+    /// Rendered as both indices separated by `,`, e.g., `3,5`.
+    Index2(u64, u64),
+    /// Child derived with [`SecretTree::digest()`](crate::SecretTree::digest()).
+    /// Derived trees record [`RecordedSegment::RedactedDigest`] in their paths instead.
+    Digest([u8; 32]),
+    /// Child derived with [`SecretTree::bind()`](crate::SecretTree::bind()). Contains
+    /// the Blake2b-128 hash of the associated data; rendered as `&` followed by the hex-encoded
+    /// hash.
+    Binding([u8; 16]),
+    /// One of the two children produced by [`SecretTree::split()`](crate::SecretTree::split()).
+    /// Rendered as `!0` for the key tree or `!1` for the RNG tree.
+    Split(SplitHalf),
+    /// Tree rotated with [`SecretTree::rotate()`](crate::SecretTree::rotate()) to the specified
+    /// generation. Rendered as `@` followed by the generation, e.g., `@2`.
+    Rotation(u32),
+}
+
+/// Segment of a derivation path [recorded](crate::SecretTree::path()) by a tree
+/// with the `track-path` feature.
+///
+/// Unlike a [`Segment`], a recorded segment may omit data that could be secret; thus,
+/// recorded paths cannot generally be used to re-derive a tree. The [`Display`](fmt::Display)
+/// implementation is the same as for [`Segment`], with redacted digests rendered
+/// as `<redacted digest>` (which is not a valid path segment).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RecordedSegment {
+    /// Segment recorded as is.
+    Segment(Segment),
+    /// Child derived with [`SecretTree::digest()`](crate::SecretTree::digest()). The digest
+    /// itself is not recorded since it may be derived from secret data.
+    RedactedDigest,
+}
+
+impl From<Segment> for RecordedSegment {
+    fn from(segment: Segment) -> Self {
+        Self::Segment(segment)
+    }
+}
+
+impl PartialEq<Segment> for RecordedSegment {
+    fn eq(&self, other: &Segment) -> bool {
+        matches!(self, Self::Segment(segment) if segment == other)
+    }
+}
+
+impl fmt::Display for RecordedSegment {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Segment(segment) => fmt::Display::fmt(segment, formatter),
+            Self::RedactedDigest => formatter.write_str("<redacted digest>"),
+        }
+    }
+}
+
+/// Binary identifier of a [child tree](crate::SecretTree::child_raw()), up to
+/// [`Self::MAX_LEN`] bytes long. Recorded in [`Segment::Bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawId([u8; SALT_LEN]);
+
+impl RawId {
+    /// Maximum byte length of an identifier (15).
+    pub const MAX_LEN: usize = SALT_LEN - 1;
+
+    /// Creates an identifier from the provided bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is longer than [`Self::MAX_LEN`].
+    pub fn new(bytes: &[u8]) -> Result<Self, LenError> {
+        let len = u8::try_from(bytes.len())
+            .ok()
+            .filter(|&len| usize::from(len) <= Self::MAX_LEN)
+            .ok_or(LenError {
+                actual: bytes.len(),
+                min: 0,
+                max: Self::MAX_LEN,
+            })?;
+        let mut salt = [0_u8; SALT_LEN];
+        salt[..bytes.len()].copy_from_slice(bytes);
+        salt[Self::MAX_LEN] = len;
+        Ok(Self(salt))
+    }
+
+    /// Returns the bytes of this identifier.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0[..usize::from(self.0[Self::MAX_LEN])]
+    }
+
+    /// Returns the salt used for derivation: identifier bytes zero-padded to [`Self::MAX_LEN`]
+    /// bytes, followed by the identifier length.
+    pub(crate) fn to_salt(self) -> [u8; SALT_LEN] {
+        self.0
+    }
+}
+
+/// Half of a [split](crate::SecretTree::split()) tree, recorded in [`Segment::Split`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplitHalf {
+    /// Tree intended for deriving a fixed-size key. Rendered as `0`.
+    Keys,
+    /// Tree intended for deriving an RNG. Rendered as `1`.
+    Rng,
+}
+
+impl SplitHalf {
+    /// Returns the index of this half used as the salt in derivation.
+    pub(crate) fn index(self) -> u64 {
+        match self {
+            Self::Keys => 0,
+            Self::Rng => 1,
+        }
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => fmt::Display::fmt(name, formatter),
+            Self::Index(index) => fmt::Display::fmt(index, formatter),
+            Self::Bytes(id) => {
+                formatter.write_str("#")?;
+                for byte in id.as_bytes() {
+                    write!(formatter, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Self::Index2(a, b) => write!(formatter, "{a},{b}"),
+            Self::Digest(digest) => {
+                for byte in digest {
+                    write!(formatter, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Self::Binding(aad_hash) => {
+                formatter.write_str("&")?;
+                for byte in aad_hash {
+                    write!(formatter, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Self::Split(half) => write!(formatter, "!{}", half.index()),
+            Self::Rotation(generation) => write!(formatter, "@{generation}"),
+        }
+    }
+}
+
+impl Segment {
+    /// Parses a single segment of a `/`-separated path. See [`SecretTree::derive_path()`]
+    /// for the syntax.
+    ///
+    /// [`SecretTree::derive_path()`]: crate::SecretTree::derive_path()
+    fn parse(segment: &str, position: usize) -> Result<Self, PathError> {
+        if segment.is_empty() {
+            return Err(PathError::EmptySegment { position });
+        }
+
+        let bytes = segment.as_bytes();
+        if let Some(half) = Self::parse_split(segment) {
+            Ok(Self::Split(half))
+        } else if let Some(generation) = Self::parse_rotation(segment) {
+            let generation = generation
+                .parse()
+                .map_err(|error| PathError::InvalidIndex { position, error })?;
+            Ok(Self::Rotation(generation))
+        } else if let Some((a, b)) = Self::parse_index2(segment) {
+            let parse = |index: &str| {
+                index
+                    .parse()
+                    .map_err(|error| PathError::InvalidIndex { position, error })
+            };
+            Ok(Self::Index2(parse(a)?, parse(b)?))
+        } else if let Some(id) = Self::parse_bytes(bytes) {
+            Ok(Self::Bytes(id))
+        } else if let Some(hex) = Self::parse_binding(bytes) {
+            let mut aad_hash = [0_u8; 16];
+            for (byte, chunk) in aad_hash.iter_mut().zip(hex.chunks_exact(2)) {
+                *byte = (hex_digit(chunk[0]) << 4) | hex_digit(chunk[1]);
+            }
+            Ok(Self::Binding(aad_hash))
+        } else if bytes.len() == 64 && bytes.iter().all(u8::is_ascii_hexdigit) {
+            let mut digest = [0_u8; 32];
+            for (byte, chunk) in digest.iter_mut().zip(bytes.chunks_exact(2)) {
+                *byte = (hex_digit(chunk[0]) << 4) | hex_digit(chunk[1]);
+            }
+            Ok(Self::Digest(digest))
+        } else if bytes.iter().all(u8::is_ascii_digit) {
+            let index = segment
+                .parse()
+                .map_err(|error| PathError::InvalidIndex { position, error })?;
+            Ok(Self::Index(index))
+        } else {
+            let name = segment
+                .parse()
+                .map_err(|error| PathError::InvalidName { position, error })?;
+            Ok(Self::Name(name))
+        }
+    }
+
+    fn parse_rotation(segment: &str) -> Option<&str> {
+        let generation = segment.strip_prefix('@')?;
+        let is_numeric = !generation.is_empty() && generation.bytes().all(|b| b.is_ascii_digit());
+        is_numeric.then_some(generation)
+    }
+
+    fn parse_index2(segment: &str) -> Option<(&str, &str)> {
+        let (a, b) = segment.split_once(',')?;
+        let is_numeric = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        (is_numeric(a) && is_numeric(b)).then_some((a, b))
+    }
+
+    fn parse_bytes(segment: &[u8]) -> Option<RawId> {
+        let hex = segment.strip_prefix(b"#")?;
+        let is_valid = hex.len() % 2 == 0 && hex.iter().all(u8::is_ascii_hexdigit);
+        if !is_valid {
+            return None;
+        }
+        let mut id_bytes = [0_u8; RawId::MAX_LEN];
+        let id_bytes = id_bytes.get_mut(..hex.len() / 2)?;
+        for (byte, chunk) in id_bytes.iter_mut().zip(hex.chunks_exact(2)) {
+            *byte = (hex_digit(chunk[0]) << 4) | hex_digit(chunk[1]);
+        }
+        RawId::new(id_bytes).ok()
+    }
+
+    fn parse_binding(segment: &[u8]) -> Option<&[u8]> {
+        let hex = segment.strip_prefix(b"&")?;
+        let is_valid = hex.len() == 32 && hex.iter().all(u8::is_ascii_hexdigit);
+        is_valid.then_some(hex)
+    }
+
+    fn parse_split(segment: &str) -> Option<SplitHalf> {
+        match segment {
+            "!0" => Some(SplitHalf::Keys),
+            "!1" => Some(SplitHalf::Rng),
+            _ => None,
+        }
+    }
+}
+
+fn hex_digit(ch: u8) -> u8 {
+    match ch {
+        b'0'..=b'9' => ch - b'0',
+        b'a'..=b'f' => ch - b'a' + 10,
+        b'A'..=b'F' => ch - b'A' + 10,
+        _ => unreachable!("checked before"),
+    }
+}
+
+/// Parses a `/`-separated path into segments. This parser is shared by path validation
+/// and derivation, so that they cannot diverge.
+pub(crate) fn parse_path(path: &str) -> impl Iterator<Item = Result<Segment, PathError>> + '_ {
+    let segments = if path.is_empty() {
+        None
+    } else {
+        Some(path.split('/'))
+    };
+    segments
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(position, segment)| Segment::parse(segment, position))
+}
+
+/// Errors that can occur when parsing a derivation path, e.g., in
+/// [`SecretTree::derive_path()`](crate::SecretTree::derive_path()).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PathError {
+    /// The path contains an empty segment (e.g., `a//b`).
+    EmptySegment {
+        /// Zero-based position of the segment in the path.
+        position: usize,
+    },
+    /// A name segment is invalid.
+    InvalidName {
+        /// Zero-based position of the segment in the path.
+        position: usize,
+        /// Underlying error.
+        error: NameError,
+    },
+    /// An index segment cannot be parsed (e.g., because of overflow).
+    InvalidIndex {
+        /// Zero-based position of the segment in the path.
+        position: usize,
+        /// Underlying error.
+        error: ParseIntError,
+    },
+    /// Deriving a segment would exceed the maximum depth of the tree.
+    Depth(DepthError),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptySegment { position } => {
+                write!(formatter, "path segment #{position} is empty")
+            }
+            Self::InvalidName { position, error } => {
+                write!(
+                    formatter,
+                    "invalid name in path segment #{position}: {error}"
+                )
+            }
+            Self::InvalidIndex { position, error } => {
+                write!(
+                    formatter,
+                    "invalid index in path segment #{position}: {error}"
+                )
+            }
+            Self::Depth(err) => fmt::Display::fmt(err, formatter),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EmptySegment { .. } => None,
+            Self::InvalidName { error, .. } => Some(error),
+            Self::InvalidIndex { error, .. } => Some(error),
+            Self::Depth(err) => Some(err),
+        }
+    }
+}
+
+/// Recorded sequence of derivation steps that can be applied to multiple trees.
+///
+/// A script is a recipe for deriving a descendant, which can be defined once and then
+/// applied to different roots. Applying a script is equivalent to manually chaining
+/// the corresponding derivation methods. Scripts are displayed and parsed in the same
+/// `/`-separated format as used by [`SecretTree::derive_path()`]; with the `serde` feature,
+/// scripts are (de)serialized in this format as well.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{DerivationScript, Name, SecretTree};
+/// # use rand::thread_rng;
+/// let mut script = DerivationScript::default();
+/// script.push_child(Name::new("accounts")).push_index(3);
+/// assert_eq!(script.to_string(), "accounts/3");
+///
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let account = script.apply(&tree);
+/// assert_eq!(account, tree.child(Name::new("accounts")).index(3));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DerivationScript {
+    segments: Vec<Segment>,
+}
+
+#[cfg(feature = "alloc")]
+impl DerivationScript {
+    /// Returns the recorded segments.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Adds a step deriving a [named child](SecretTree::child()).
+    pub fn push_child(&mut self, name: Name) -> &mut Self {
+        self.segments.push(Segment::Name(name));
+        self
+    }
+
+    /// Adds a step deriving an [indexed child](SecretTree::index()).
+    pub fn push_index(&mut self, index: u64) -> &mut Self {
+        self.segments.push(Segment::Index(index));
+        self
+    }
+
+    /// Adds a step deriving a [digest child](SecretTree::digest()).
+    pub fn push_digest(&mut self, digest: [u8; 32]) -> &mut Self {
+        self.segments.push(Segment::Digest(digest));
+        self
+    }
+
+    /// Applies this script to the specified tree. If the script is empty, returns a copy
+    /// of the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [maximum depth](SecretTree::with_max_depth()) of the tree
+    /// is exceeded.
+    pub fn try_apply(&self, tree: &SecretTree) -> Result<SecretTree, DepthError> {
+        let Some((first_segment, segments)) = self.segments.split_first() else {
+            return Ok(tree.duplicate());
+        };
+        let mut tree = tree.try_derive_segment(*first_segment)?;
+        for &segment in segments {
+            tree = tree.try_derive_segment(segment)?;
+        }
+        Ok(tree)
+    }
+
+    /// Applies this script to the specified tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](SecretTree::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    pub fn apply(&self, tree: &SecretTree) -> SecretTree {
+        match self.try_apply(tree) {
+            Ok(tree) => tree,
+            Err(err) => panic!("Failed deriving a child from `SecretTree`: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Vec<Segment>> for DerivationScript {
+    fn from(segments: Vec<Segment>) -> Self {
+        Self { segments }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for DerivationScript {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str("/")?;
+            }
+            fmt::Display::fmt(segment, formatter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a script from a `/`-separated path, with the same syntax
+/// as [`SecretTree::derive_path()`].
+#[cfg(feature = "alloc")]
+impl FromStr for DerivationScript {
+    type Err = PathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let segments = parse_path(path).collect::<Result<_, _>>()?;
+        Ok(Self { segments })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_path() {
+        let digest_str = "01".repeat(32);
+        let binding_str = "ff".repeat(16);
+        let path = format!("foo/42/{digest_str}/bar/!1/@3/4,2/#0a0b/#/#0000/&{binding_str}");
+        let segments: Vec<_> = parse_path(&path).collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            segments,
+            [
+                Segment::Name(Name::new("foo")),
+                Segment::Index(42),
+                Segment::Digest([1; 32]),
+                Segment::Name(Name::new("bar")),
+                Segment::Split(SplitHalf::Rng),
+                Segment::Rotation(3),
+                Segment::Index2(4, 2),
+                Segment::Bytes(RawId::new(&[10, 11]).unwrap()),
+                Segment::Bytes(RawId::new(&[]).unwrap()),
+                Segment::Bytes(RawId::new(&[0, 0]).unwrap()),
+                Segment::Binding([0xff; 16]),
+            ]
+        );
+
+        let rendered: Vec<_> = segments.iter().map(ToString::to_string).collect();
+        assert_eq!(rendered.join("/"), path);
+        assert_eq!(parse_path("").count(), 0);
+    }
+
+    #[test]
+    fn path_parsing_errors() {
+        let err = parse_path("foo//bar").find_map(Result::err).unwrap();
+        assert!(matches!(err, PathError::EmptySegment { position: 1 }));
+
+        let err = parse_path("foo/this_name_is_too_long")
+            .find_map(Result::err)
+            .unwrap();
+        assert!(matches!(
+            err,
+            PathError::InvalidName {
+                position: 1,
+                error: NameError::TooLong
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "invalid name in path segment #1: name is too long, 0..=16 bytes expected"
+        );
+
+        let err = parse_path("99999999999999999999999")
+            .find_map(Result::err)
+            .unwrap();
+        assert!(matches!(err, PathError::InvalidIndex { position: 0, .. }));
+        let err = parse_path("foo/@99999999999")
+            .find_map(Result::err)
+            .unwrap();
+        assert!(matches!(err, PathError::InvalidIndex { position: 1, .. }));
+
+        let err = parse_path("1,99999999999999999999999")
+            .find_map(Result::err)
+            .unwrap();
+        assert!(matches!(err, PathError::InvalidIndex { position: 0, .. }));
+
+        // Segments with `,` not separating two indices are valid names.
+        let segments: Vec<_> = parse_path("1,/,2/1,2,3").collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            segments,
+            [
+                Segment::Name(Name::new("1,")),
+                Segment::Name(Name::new(",2")),
+                Segment::Name(Name::new("1,2,3"))
+            ]
+        );
+
+        // `#` not followed by an even number of hex digits is a valid name.
+        let segments: Vec<_> = parse_path("#a/#xy").collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            segments,
+            [
+                Segment::Name(Name::new("#a")),
+                Segment::Name(Name::new("#xy"))
+            ]
+        );
+        let long_hex = format!("#{}", "ab".repeat(17));
+        let err = parse_path(&long_hex).find_map(Result::err).unwrap();
+        assert!(matches!(err, PathError::InvalidName { position: 0, .. }));
+
+        // `@` not followed by digits is a valid name.
+        let segments: Vec<_> = parse_path("@/@x").collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            segments,
+            [
+                Segment::Name(Name::new("@")),
+                Segment::Name(Name::new("@x"))
+            ]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn derivation_scripts() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaChaRng;
+
+        let mut script = DerivationScript::default();
+        script
+            .push_child(Name::new("foo"))
+            .push_index(1)
+            .push_digest([0xcd; 32]);
+        let script_str = script.to_string();
+        assert_eq!(script_str, format!("foo/1/{}", "cd".repeat(32)));
+        assert_eq!(script_str.parse::<DerivationScript>().unwrap(), script);
+
+        for seed in 0..3 {
+            let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(seed));
+            let expected = tree.child(Name::new("foo")).index(1).digest(&[0xcd; 32]);
+            assert_eq!(script.apply(&tree), expected);
+            assert_eq!(tree.derive_path(&script_str).unwrap(), expected);
+        }
+
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(0)).with_max_depth(2);
+        script.try_apply(&tree).unwrap_err();
+        assert_eq!(DerivationScript::default().apply(&tree), tree);
+    }
+}
@@ -0,0 +1,69 @@
+//! Interoperability with `rand_core` v0.9.
+
+use rand_chacha_09::ChaChaRng;
+use rand_core_09::{CryptoRng, SeedableRng};
+
+use crate::{SecretTree, Seed};
+
+impl Seed {
+    /// Generates a random seed using the provided RNG implementing `rand_core` v0.9 traits.
+    pub fn new_v09<R: CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        Self::init_with(|seed_bytes| rng.fill_bytes(seed_bytes))
+    }
+}
+
+impl SecretTree {
+    /// Generates a tree by sampling its seed from the supplied RNG implementing `rand_core` v0.9
+    /// traits. Otherwise, this is equivalent to [`Self::new()`].
+    pub fn new_v09<R: CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_seed(Seed::new_v09(rng))
+    }
+
+    /// Converts this tree into a CSPRNG implementing `rand_core` v0.9 traits. The RNG output
+    /// is identical to the RNG returned by [`Self::rng()`]; the same security considerations
+    /// apply.
+    pub fn rng_v09(self) -> ChaChaRng {
+        let mut seed = <ChaChaRng as SeedableRng>::Seed::default();
+        self.derive_rng_seed(0, &mut seed);
+        ChaChaRng::from_seed(seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core_09::RngCore as _;
+
+    use super::*;
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn rng_output_is_identical_across_versions() {
+        use rand::{Rng, SeedableRng as _};
+        use rand_chacha::ChaChaRng as ChaChaRng03;
+
+        use crate::Name;
+
+        let tree = SecretTree::new(&mut ChaChaRng03::seed_from_u64(123));
+        let child = tree.child(Name::new("rng"));
+        let mut rng = child.rng();
+        let mut rng_v09 = tree.child(Name::new("rng")).rng_v09();
+
+        for _ in 0..10 {
+            assert_eq!(rng.gen::<u32>(), rng_v09.next_u32());
+            assert_eq!(rng.gen::<u64>(), rng_v09.next_u64());
+        }
+        let mut bytes = [0_u8; 100];
+        rng.fill(&mut bytes[..]);
+        let mut bytes_v09 = [0_u8; 100];
+        rng_v09.fill_bytes(&mut bytes_v09);
+        assert_eq!(bytes, bytes_v09);
+    }
+
+    #[test]
+    fn creating_tree_with_new_rng() {
+        let tree = SecretTree::new_v09(&mut ChaChaRng::seed_from_u64(123));
+        let mut expected_seed = [0_u8; 32];
+        ChaChaRng::seed_from_u64(123).fill_bytes(&mut expected_seed);
+        assert_eq!(*tree.seed().expose_secret(), expected_seed);
+    }
+}
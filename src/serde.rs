@@ -0,0 +1,277 @@
+//! `serde` support for non-secret types and [`SecretTreeSet`](crate::SecretTreeSet).
+
+#[cfg(feature = "alloc")]
+use secrecy::zeroize::Zeroizing;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use core::{fmt, str::FromStr};
+
+use crate::Name;
+#[cfg(feature = "alloc")]
+use crate::{DerivationScript, SecretTree, SecretTreeSet, Seed, SEED_LEN};
+
+/// Serializes a name as its string form.
+impl Serialize for Name {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+/// Deserializes a name from a string, with the same validation as the [`FromStr`] implementation.
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl de::Visitor<'_> for NameVisitor {
+            type Value = Name;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("name (0..=16 bytes without null chars)")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Name::from_str(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+/// Serializes a script in the `/`-separated path format.
+#[cfg(feature = "alloc")]
+impl Serialize for DerivationScript {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a script from the `/`-separated path format, with the same validation
+/// as the [`FromStr`] implementation.
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for DerivationScript {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ScriptVisitor;
+
+        impl de::Visitor<'_> for ScriptVisitor {
+            type Value = DerivationScript;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("derivation path")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                DerivationScript::from_str(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ScriptVisitor)
+    }
+}
+
+/// Hex-encoded tree seed.
+#[cfg(feature = "alloc")]
+struct HexSeed<'a>(&'a [u8; SEED_LEN]);
+
+/// Serializes the seed via a stack buffer, which is zeroized after use, rather than
+/// an intermediate `String`.
+#[cfg(feature = "alloc")]
+impl Serialize for HexSeed<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let mut hex = Zeroizing::new([0_u8; 2 * SEED_LEN]);
+        for (chunk, &byte) in hex.chunks_exact_mut(2).zip(self.0) {
+            chunk[0] = HEX_DIGITS[usize::from(byte >> 4)];
+            chunk[1] = HEX_DIGITS[usize::from(byte & 0xf)];
+        }
+        let hex_str = core::str::from_utf8(&*hex).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(hex_str)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn hex_value(ch: u8) -> Option<u8> {
+    match ch {
+        b'0'..=b'9' => Some(ch - b'0'),
+        b'a'..=b'f' => Some(ch - b'a' + 10),
+        b'A'..=b'F' => Some(ch - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Deserializes a tree from a hex-encoded seed. Error messages never include the seed.
+#[cfg(feature = "alloc")]
+struct HexTree(SecretTree);
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for HexTree {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HexTreeVisitor;
+
+        impl de::Visitor<'_> for HexTreeVisitor {
+            type Value = HexTree;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "hex-encoded seed ({} hex digits)", 2 * SEED_LEN)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if value.len() != 2 * SEED_LEN {
+                    return Err(E::invalid_length(value.len(), &self));
+                }
+
+                let mut is_valid = true;
+                let seed = Seed::init_with(|bytes| {
+                    let hex_pairs = value.as_bytes().chunks_exact(2);
+                    for (byte, pair) in bytes.iter_mut().zip(hex_pairs) {
+                        if let (Some(hi), Some(lo)) = (hex_value(pair[0]), hex_value(pair[1])) {
+                            *byte = (hi << 4) | lo;
+                        } else {
+                            is_valid = false;
+                            break;
+                        }
+                    }
+                });
+                if !is_valid {
+                    return Err(E::custom("seed contains non-hex chars"));
+                }
+                SecretTree::from_seed_checked(seed)
+                    .map(HexTree)
+                    .map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(HexTreeVisitor)
+    }
+}
+
+/// Serializes the set as a map from domain names to hex-encoded tree seeds.
+///
+/// Only seeds are serialized; the depth and the maximum depth of trees are discarded.
+/// **The output contains secrets** and must be protected accordingly.
+#[cfg(feature = "alloc")]
+impl Serialize for SecretTreeSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.iter()
+                .map(|(domain, tree)| (domain, HexSeed(tree.derivation_seed()))),
+        )
+    }
+}
+
+/// Deserializes the set from a map from domain names to hex-encoded tree seeds.
+/// Duplicate domains and all-zero seeds are rejected.
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for SecretTreeSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SetVisitor;
+
+        impl<'de> de::Visitor<'de> for SetVisitor {
+            type Value = SecretTreeSet;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("map from domain names to hex-encoded seeds")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut set = SecretTreeSet::new();
+                while let Some((domain, HexTree(tree))) =
+                    map.next_entry::<alloc::string::String, HexTree>()?
+                {
+                    if set.get(&domain).is_some() {
+                        let msg = format_args!("duplicate domain `{domain}`");
+                        return Err(de::Error::custom(msg));
+                    }
+                    set.insert(domain, tree);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_map(SetVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_roundtrip() {
+        for name in ["", "test", "Overly long name"] {
+            let name = Name::new(name);
+            let json = serde_json::to_string(&name).unwrap();
+            assert_eq!(json, format!("\"{name}\""));
+            let restored: Name = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, name);
+            assert_eq!(restored.0, name.0);
+        }
+    }
+
+    #[test]
+    fn name_deserialization_errors() {
+        let err = serde_json::from_str::<Name>("\"Overly long name?\"").unwrap_err();
+        assert!(err.to_string().contains("name is too long"), "{err}");
+        let err = serde_json::from_str::<Name>("\"a\\u0000b\"").unwrap_err();
+        assert!(err.to_string().contains("null char"), "{err}");
+        let err = serde_json::from_str::<Name>("42").unwrap_err();
+        assert!(err.to_string().contains("expected name"), "{err}");
+    }
+
+    #[test]
+    fn secret_tree_set_roundtrip() {
+        let mut set = SecretTreeSet::new();
+        set.insert("payments", SecretTree::from_slice(&[0xab; 32]).unwrap());
+        set.insert("auth", SecretTree::from_slice(&[1; 32]).unwrap());
+        let json = serde_json::to_value(&set).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "auth": "01".repeat(32),
+                "payments": "ab".repeat(32),
+            })
+        );
+
+        let restored: SecretTreeSet = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.len(), 2);
+        for (domain, tree) in set.iter() {
+            assert_eq!(restored.domain(domain), tree);
+        }
+    }
+
+    #[test]
+    fn secret_tree_set_deserialization_errors() {
+        let err = serde_json::from_str::<SecretTreeSet>(r#"{"a": "0101"}"#).unwrap_err();
+        assert!(err.to_string().contains("invalid length 4"), "{err}");
+
+        let json = format!(r#"{{"a": "{}"}}"#, "0g".repeat(32));
+        let err = serde_json::from_str::<SecretTreeSet>(&json).unwrap_err();
+        assert!(err.to_string().contains("non-hex chars"), "{err}");
+
+        let json = format!(r#"{{"a": "{}"}}"#, "00".repeat(32));
+        let err = serde_json::from_str::<SecretTreeSet>(&json).unwrap_err();
+        assert!(err.to_string().contains("zero bytes only"), "{err}");
+
+        let seed = "01".repeat(32);
+        let json = format!(r#"{{"a": "{seed}", "a": "{seed}"}}"#);
+        let err = serde_json::from_str::<SecretTreeSet>(&json).unwrap_err();
+        assert!(err.to_string().contains("duplicate domain `a`"), "{err}");
+    }
+
+    #[test]
+    fn derivation_script_roundtrip() {
+        let mut script = DerivationScript::default();
+        script.push_child(Name::new("foo")).push_index(42);
+        let json = serde_json::to_string(&script).unwrap();
+        assert_eq!(json, "\"foo/42\"");
+        let restored: DerivationScript = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, script);
+
+        let err = serde_json::from_str::<DerivationScript>("\"foo//42\"").unwrap_err();
+        assert!(
+            err.to_string().contains("path segment #1 is empty"),
+            "{err}"
+        );
+    }
+}
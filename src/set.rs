@@ -0,0 +1,141 @@
+//! Sets of independent root trees.
+
+use alloc::{collections::BTreeMap, string::String};
+
+use crate::SecretTree;
+
+/// Set of independent [`SecretTree`]s keyed by a domain name (e.g., `"payments"`).
+/// This is useful if an application has several root seeds, e.g., one per security domain.
+///
+/// Tree seeds are zeroized on drop, as with standalone trees.
+///
+/// With the `serde` feature, the set can be (de)serialized as a map from domain names
+/// to hex-encoded tree seeds.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{Name, SecretTree, SecretTreeSet};
+/// # use rand::thread_rng;
+/// let mut set = SecretTreeSet::new();
+/// set.insert("payments", SecretTree::new(&mut thread_rng()));
+/// set.insert("messaging", SecretTree::new(&mut thread_rng()));
+///
+/// let payment_key = set.domain("payments").child(Name::new("signing"));
+/// assert!(set.get("auth").is_none());
+/// assert_eq!(set.domains().collect::<Vec<_>>(), ["messaging", "payments"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SecretTreeSet {
+    trees: BTreeMap<String, SecretTree>,
+}
+
+impl SecretTreeSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of domains in this set.
+    pub fn len(&self) -> usize {
+        self.trees.len()
+    }
+
+    /// Checks whether this set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    /// Iterates over domain names in this set in the lexicographic order.
+    pub fn domains(&self) -> impl Iterator<Item = &str> + '_ {
+        self.trees.keys().map(String::as_str)
+    }
+
+    /// Inserts a tree for the specified domain, returning the previous tree for the domain,
+    /// if any.
+    pub fn insert(&mut self, domain: impl Into<String>, tree: SecretTree) -> Option<SecretTree> {
+        self.trees.insert(domain.into(), tree)
+    }
+
+    /// Removes the tree for the specified domain and returns it.
+    pub fn remove(&mut self, domain: &str) -> Option<SecretTree> {
+        self.trees.remove(domain)
+    }
+
+    /// Returns the tree for the specified domain, or `None` if the domain is not in the set.
+    pub fn get(&self, domain: &str) -> Option<&SecretTree> {
+        self.trees.get(domain)
+    }
+
+    /// Returns the tree for the specified domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain is not in the set.
+    #[track_caller]
+    pub fn domain(&self, domain: &str) -> &SecretTree {
+        match self.get(domain) {
+            Some(tree) => tree,
+            None => panic!("Domain `{domain}` is not in `SecretTreeSet`"),
+        }
+    }
+
+    /// Iterates over `(domain, tree)` pairs in this set in the lexicographic order of domains.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SecretTree)> + '_ {
+        self.trees
+            .iter()
+            .map(|(domain, tree)| (domain.as_str(), tree))
+    }
+}
+
+impl<D: Into<String>> FromIterator<(D, SecretTree)> for SecretTreeSet {
+    fn from_iter<I: IntoIterator<Item = (D, SecretTree)>>(iter: I) -> Self {
+        Self {
+            trees: iter
+                .into_iter()
+                .map(|(domain, tree)| (domain.into(), tree))
+                .collect(),
+        }
+    }
+}
+
+impl<D: Into<String>> Extend<(D, SecretTree)> for SecretTreeSet {
+    fn extend<I: IntoIterator<Item = (D, SecretTree)>>(&mut self, iter: I) {
+        self.trees
+            .extend(iter.into_iter().map(|(domain, tree)| (domain.into(), tree)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_set_operations() {
+        let mut set: SecretTreeSet = [
+            ("a", SecretTree::from_slice(&[1; 32]).unwrap()),
+            ("b", SecretTree::from_slice(&[2; 32]).unwrap()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(*set.domain("a"), SecretTree::from_slice(&[1; 32]).unwrap());
+
+        let prev_tree = set.insert("a", SecretTree::from_slice(&[3; 32]).unwrap());
+        assert_eq!(
+            prev_tree.unwrap(),
+            SecretTree::from_slice(&[1; 32]).unwrap()
+        );
+        assert_eq!(*set.domain("a"), SecretTree::from_slice(&[3; 32]).unwrap());
+
+        let _ = set.remove("b").unwrap();
+        assert_eq!(set.domains().collect::<Vec<_>>(), ["a"]);
+        assert!(set.get("b").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Domain `c` is not in `SecretTreeSet`")]
+    fn missing_domain_panics() {
+        let _ = SecretTreeSet::new().domain("c");
+    }
+}
@@ -0,0 +1,419 @@
+//! [SLIP-0010] key derivation for the ed25519 curve.
+//!
+//! This derivation scheme is **unrelated** to the native derivation performed by [`SecretTree`];
+//! it is provided for interoperability with hardware wallets and other software implementing
+//! SLIP-0010. Keys derived with SLIP-0010 never coincide with keys derived natively,
+//! and SLIP-0010 paths (e.g., `m/44'/0'`) cannot be used with [`SecretTree::derive_path()`].
+//!
+//! Since ed25519 only supports hardened derivation, all indices in paths must be hardened.
+//!
+//! [SLIP-0010]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use core::{fmt, num::ParseIntError};
+
+use hmac::{Hmac, Mac};
+use secrecy::{
+    zeroize::{Zeroize, Zeroizing},
+    ExposeSecret, SecretBox,
+};
+use sha2::Sha512;
+
+use crate::{
+    contexts,
+    kdf::{derive_key, Index},
+    SecretTree, SEED_LEN,
+};
+
+/// Offset for hardened indices.
+pub const HARDENED_OFFSET: u32 = 1 << 31;
+/// HMAC key used to derive the master key from a seed.
+const MASTER_HMAC_KEY: &[u8] = b"ed25519 seed";
+
+/// Extended ed25519 key, i.e., a secret key together with a chain code.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{slip10::ExtendedKey, SecretTree, Name};
+/// # use rand::thread_rng;
+/// # use secrecy::ExposeSecret;
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let master_key = tree.child(Name::new("wallet")).slip10_master();
+/// let account_key = master_key.derive_path("m/44'/501'/0'")?;
+/// let signing_key =
+///     ed25519::SigningKey::from_bytes(account_key.secret_key().expose_secret());
+/// # Ok::<_, secret_tree::slip10::PathError>(())
+/// ```
+#[derive(Debug)]
+#[must_use = "A key should be used for signing or to derive a child key"]
+pub struct ExtendedKey {
+    secret_key: SecretBox<[u8; 32]>,
+    chain_code: SecretBox<[u8; 32]>,
+}
+
+impl ExtendedKey {
+    /// Derives the master key from the provided seed. SLIP-0010 expects seeds
+    /// with length `16..=64` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::slip10::ExtendedKey;
+    /// # use secrecy::ExposeSecret;
+    /// // Test vector 1 from the SLIP-0010 spec
+    /// let seed = hex::decode("000102030405060708090a0b0c0d0e0f")?;
+    /// let key = ExtendedKey::master(&seed).derive_path("m/0H")?;
+    /// assert_eq!(
+    ///     hex::encode(key.secret_key().expose_secret()),
+    ///     "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn master(seed: &[u8]) -> Self {
+        Self::from_hmac(MASTER_HMAC_KEY, &[seed])
+    }
+
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        for chunk in data {
+            mac.update(chunk);
+        }
+        let mut output = mac.finalize().into_bytes();
+        let (secret_key, chain_code) = output.split_at(32);
+        let key = Self {
+            secret_key: SecretBox::init_with_mut(|bytes: &mut [u8; 32]| {
+                bytes.copy_from_slice(secret_key);
+            }),
+            chain_code: SecretBox::init_with_mut(|bytes: &mut [u8; 32]| {
+                bytes.copy_from_slice(chain_code);
+            }),
+        };
+        output.as_mut_slice().zeroize();
+        key
+    }
+
+    /// Returns the secret key. For ed25519, this is the secret seed of the signing key.
+    pub fn secret_key(&self) -> &SecretBox<[u8; 32]> {
+        &self.secret_key
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> &SecretBox<[u8; 32]> {
+        &self.chain_code
+    }
+
+    /// Derives a child key with the specified hardened index. The index is hardened
+    /// automatically; indices with [`HARDENED_OFFSET`] already applied are accepted as well.
+    pub fn derive_hardened(&self, index: u32) -> Self {
+        let index = index | HARDENED_OFFSET;
+        Self::from_hmac(
+            self.chain_code.expose_secret(),
+            &[&[0], self.secret_key.expose_secret(), &index.to_be_bytes()],
+        )
+    }
+
+    /// Derives a key using the specified BIP32-style path relative to this key,
+    /// e.g., `m/44'/0'/1'`. Hardened indices may be marked with `'`, `h` or `H`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is malformed or contains non-hardened indices.
+    pub fn derive_path(&self, path: &str) -> Result<Self, PathError> {
+        let mut segments = path.split('/');
+        if segments.next() != Some("m") {
+            return Err(PathError::NoMaster);
+        }
+
+        let mut key = self.duplicate();
+        for (position, segment) in segments.enumerate() {
+            key = key.derive_hardened(parse_index(segment, position)?);
+        }
+        Ok(key)
+    }
+
+    fn duplicate(&self) -> Self {
+        Self {
+            secret_key: SecretBox::init_with_mut(|bytes: &mut [u8; 32]| {
+                *bytes = *self.secret_key.expose_secret();
+            }),
+            chain_code: SecretBox::init_with_mut(|bytes: &mut [u8; 32]| {
+                *bytes = *self.chain_code.expose_secret();
+            }),
+        }
+    }
+}
+
+fn parse_index(segment: &str, position: usize) -> Result<u32, PathError> {
+    if segment.is_empty() {
+        return Err(PathError::EmptySegment { position });
+    }
+    let index = segment
+        .strip_suffix(['\'', 'h', 'H'])
+        .ok_or(PathError::NotHardened { position })?;
+    let index: u32 = index
+        .parse()
+        .map_err(|error| PathError::InvalidIndex { position, error })?;
+    if index >= HARDENED_OFFSET {
+        return Err(PathError::IndexOutOfRange { position });
+    }
+    Ok(index)
+}
+
+/// Errors that can occur when parsing a SLIP-0010 derivation path in
+/// [`ExtendedKey::derive_path()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PathError {
+    /// The path does not start with the `m` segment.
+    NoMaster,
+    /// The path contains an empty segment (e.g., `m//0'`).
+    EmptySegment {
+        /// Zero-based position of the segment in the path, not counting the `m` segment.
+        position: usize,
+    },
+    /// An index is not hardened, which is not supported for ed25519.
+    NotHardened {
+        /// Zero-based position of the segment in the path, not counting the `m` segment.
+        position: usize,
+    },
+    /// An index cannot be parsed.
+    InvalidIndex {
+        /// Zero-based position of the segment in the path, not counting the `m` segment.
+        position: usize,
+        /// Underlying error.
+        error: ParseIntError,
+    },
+    /// An index is greater or equal to [`HARDENED_OFFSET`].
+    IndexOutOfRange {
+        /// Zero-based position of the segment in the path, not counting the `m` segment.
+        position: usize,
+    },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoMaster => formatter.write_str("path does not start with `m` segment"),
+            Self::EmptySegment { position } => {
+                write!(formatter, "path segment #{position} is empty")
+            }
+            Self::NotHardened { position } => {
+                write!(
+                    formatter,
+                    "index in path segment #{position} is not hardened"
+                )
+            }
+            Self::InvalidIndex { position, error } => {
+                write!(
+                    formatter,
+                    "invalid index in path segment #{position}: {error}"
+                )
+            }
+            Self::IndexOutOfRange { position } => {
+                write!(formatter, "index in path segment #{position} is too large")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidIndex { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl SecretTree {
+    /// Derives the SLIP-0010 master key for the ed25519 curve from this tree.
+    /// See the [`slip10`](crate::slip10) module docs for details.
+    ///
+    /// The SLIP-0010 seed (32 bytes) is derived from the tree seed with a dedicated
+    /// personalization ([`contexts::SLIP10_SEED`]); the tree seed itself is never used
+    /// as HMAC input. Thus, it is safe to derive other secrets from the same tree.
+    pub fn slip10_master(&self) -> ExtendedKey {
+        let mut slip10_seed = Zeroizing::new([0_u8; SEED_LEN]);
+        derive_key(
+            &mut *slip10_seed,
+            Index::None,
+            contexts::SLIP10_SEED,
+            self.derivation_seed(),
+        );
+        ExtendedKey::master(&*slip10_seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestVector {
+        path: &'static str,
+        chain_code: &'static str,
+        secret_key: &'static str,
+    }
+
+    fn check_vectors(seed: &str, vectors: &[TestVector]) {
+        let master_key = ExtendedKey::master(&hex::decode(seed).unwrap());
+        for vector in vectors {
+            let key = master_key.derive_path(vector.path).unwrap();
+            assert_eq!(
+                hex::encode(key.chain_code.expose_secret()),
+                vector.chain_code,
+                "{}",
+                vector.path
+            );
+            assert_eq!(
+                hex::encode(key.secret_key.expose_secret()),
+                vector.secret_key,
+                "{}",
+                vector.path
+            );
+        }
+    }
+
+    #[test]
+    fn spec_test_vector_1() {
+        const VECTORS: &[TestVector] = &[
+            TestVector {
+                path: "m",
+                chain_code: "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                secret_key: "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            },
+            TestVector {
+                path: "m/0H",
+                chain_code: "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                secret_key: "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            },
+            TestVector {
+                path: "m/0H/1H",
+                chain_code: "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                secret_key: "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            },
+            TestVector {
+                path: "m/0H/1H/2H",
+                chain_code: "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                secret_key: "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            },
+            TestVector {
+                path: "m/0H/1H/2H/2H",
+                chain_code: "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                secret_key: "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+            },
+            TestVector {
+                path: "m/0H/1H/2H/2H/1000000000H",
+                chain_code: "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                secret_key: "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            },
+        ];
+
+        check_vectors("000102030405060708090a0b0c0d0e0f", VECTORS);
+    }
+
+    #[test]
+    fn spec_test_vector_2() {
+        const SEED: &str = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2\
+                            9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+        const VECTORS: &[TestVector] = &[
+            TestVector {
+                path: "m",
+                chain_code: "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b",
+                secret_key: "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012",
+            },
+            TestVector {
+                path: "m/0H",
+                chain_code: "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d",
+                secret_key: "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635",
+            },
+            TestVector {
+                path: "m/0H/2147483647H",
+                chain_code: "138f0b2551bcafeca6ff2aa88ba8ed0ed8de070841f0c4ef0165df8181eaad7f",
+                secret_key: "ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4",
+            },
+            TestVector {
+                path: "m/0H/2147483647H/1H",
+                chain_code: "73bd9fff1cfbde33a1b846c27085f711c0fe2d66fd32e139d3ebc28e5a4a6b90",
+                secret_key: "3757c7577170179c7868353ada796c839135b3d30554bbb74a4b1e4a5a58505c",
+            },
+            TestVector {
+                path: "m/0H/2147483647H/1H/2147483646H",
+                chain_code: "0902fe8a29f9140480a00ef244bd183e8a13288e4412d8389d140aac1794825a",
+                secret_key: "5837736c89570de861ebc173b1086da4f505d4adb387c6a1b1342d5e4ac9ec72",
+            },
+            TestVector {
+                path: "m/0H/2147483647H/1H/2147483646H/2H",
+                chain_code: "5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4",
+                secret_key: "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d",
+            },
+        ];
+
+        check_vectors(SEED, VECTORS);
+    }
+
+    #[test]
+    fn spec_public_key() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master_key = ExtendedKey::master(&seed);
+        let signing_key = ed25519::SigningKey::from_bytes(master_key.secret_key.expose_secret());
+        assert_eq!(
+            hex::encode(signing_key.verifying_key().as_bytes()),
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"
+        );
+    }
+
+    #[test]
+    fn hardened_index_markers_are_equivalent() {
+        let master_key = ExtendedKey::master(&[1; 32]);
+        let expected = master_key.derive_hardened(5).derive_hardened(7);
+        for path in ["m/5'/7'", "m/5h/7h", "m/5H/7'"] {
+            let key = master_key.derive_path(path).unwrap();
+            assert_eq!(
+                key.secret_key.expose_secret(),
+                expected.secret_key.expose_secret()
+            );
+        }
+
+        let key = master_key.derive_hardened(5 | HARDENED_OFFSET);
+        let expected = master_key.derive_hardened(5);
+        assert_eq!(
+            key.secret_key.expose_secret(),
+            expected.secret_key.expose_secret()
+        );
+    }
+
+    #[test]
+    fn path_errors() {
+        let master_key = ExtendedKey::master(&[1; 32]);
+        let err = master_key.derive_path("0'/1'").unwrap_err();
+        assert!(matches!(err, PathError::NoMaster));
+        let err = master_key.derive_path("m/0'//1'").unwrap_err();
+        assert!(matches!(err, PathError::EmptySegment { position: 1 }));
+        let err = master_key.derive_path("m/0'/1").unwrap_err();
+        assert!(matches!(err, PathError::NotHardened { position: 1 }));
+        let err = master_key.derive_path("m/-1'").unwrap_err();
+        assert!(matches!(err, PathError::InvalidIndex { position: 0, .. }));
+        let err = master_key.derive_path("m/2147483648'").unwrap_err();
+        assert!(matches!(err, PathError::IndexOutOfRange { position: 0 }));
+    }
+
+    #[test]
+    fn tree_master_key_uses_derived_seed() {
+        let tree = SecretTree::from_slice(&[5; 32]).unwrap();
+        let master_key = tree.slip10_master();
+        let mut slip10_seed = [0_u8; SEED_LEN];
+        tree.derive_raw(0, contexts::SLIP10_SEED, &mut slip10_seed);
+        let expected = ExtendedKey::master(&slip10_seed);
+        assert_eq!(
+            master_key.secret_key.expose_secret(),
+            expected.secret_key.expose_secret()
+        );
+
+        let raw_seed_key = ExtendedKey::master(&[5; 32]);
+        assert_ne!(
+            master_key.secret_key.expose_secret(),
+            raw_seed_key.secret_key.expose_secret()
+        );
+    }
+}
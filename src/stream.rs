@@ -0,0 +1,106 @@
+//! Streaming derivation of digest children.
+
+use blake2::{
+    digest::{consts::U32, Digest},
+    Blake2b,
+};
+
+use core::fmt;
+use std::io;
+
+use crate::SecretTree;
+
+/// Writer hashing its input with Blake2b-256 in order to derive a [digest child](SecretTree::digest())
+/// of a [`SecretTree`]. Created with [`SecretTree::digest_stream()`].
+///
+/// This allows deriving children from large inputs (e.g., files) without loading them
+/// into memory. The derived child is equivalent to `tree.digest(&digest)`, where `digest`
+/// is the unkeyed Blake2b-256 hash of the written bytes (no salt or personalization).
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::SecretTree;
+/// # use rand::thread_rng;
+/// use std::io::{self, Read, Write};
+///
+/// let tree = SecretTree::new(&mut thread_rng());
+/// let mut writer = tree.digest_stream();
+/// io::copy(&mut io::repeat(0xaa).take(1 << 20), &mut writer)?;
+/// writer.write_all(b"trailer")?;
+/// let child = writer.finish();
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct DigestWriter<'a> {
+    tree: &'a SecretTree,
+    hasher: Blake2b<U32>,
+}
+
+impl fmt::Debug for DigestWriter<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("DigestWriter")
+            .finish_non_exhaustive()
+    }
+}
+
+impl DigestWriter<'_> {
+    /// Finishes hashing and derives the digest child.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [maximum depth](SecretTree::with_max_depth()) of the tree is exceeded.
+    #[track_caller]
+    pub fn finish(self) -> SecretTree {
+        let digest: [u8; 32] = self.hasher.finalize().into();
+        self.tree.digest(&digest)
+    }
+}
+
+impl io::Write for DigestWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SecretTree {
+    /// Creates a writer that hashes the written bytes and then derives
+    /// a [digest child](Self::digest()) of this tree. See [`DigestWriter`] docs for details.
+    pub fn digest_stream(&self) -> DigestWriter<'_> {
+        DigestWriter {
+            tree: self,
+            hasher: Blake2b::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn streaming_digest_is_equivalent_to_digest() {
+        let tree = SecretTree::new(&mut ChaChaRng::seed_from_u64(123));
+        let data = vec![0x42_u8; 10_000];
+
+        let mut writer = tree.digest_stream();
+        for chunk in data.chunks(333) {
+            writer.write_all(chunk).unwrap();
+        }
+        let child = writer.finish();
+
+        let digest: [u8; 32] = Blake2b::<U32>::digest(&data).into();
+        assert_eq!(child, tree.digest(&digest));
+        assert_eq!(child.depth(), 1);
+    }
+}
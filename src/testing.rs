@@ -0,0 +1,66 @@
+//! Deterministic trees for test fixtures.
+
+use crate::{
+    contexts,
+    kdf::{derive_key, hash_to_salt, Index},
+    SecretTree, Seed, SEED_LEN,
+};
+
+impl SecretTree {
+    /// Creates a tree with a well-known seed derived from the provided label.
+    ///
+    /// The seed is derived in the same way as other keys, using the all-zero 32-byte key,
+    /// the Blake2b-128 hash of the UTF-8 encoded label as salt and [`TEST_SEED`](contexts::TEST_SEED)
+    /// personalization. As such, the same label always produces the same tree, which is useful
+    /// to share reproducible fixtures across tests (including tests in other projects).
+    ///
+    /// # Security
+    ///
+    /// **The produced tree is insecure.** Anyone knowing the label can recover the tree seed
+    /// and all secrets derived from it. This method must only be used in tests and never
+    /// in production.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use secret_tree::SecretTree;
+    /// let tree = SecretTree::from_test_seed("alice");
+    /// let same_tree = SecretTree::from_test_seed("alice");
+    /// assert_eq!(tree, same_tree);
+    /// assert_ne!(tree, SecretTree::from_test_seed("bob"));
+    /// ```
+    pub fn from_test_seed(label: &str) -> Self {
+        Self::from_seed(Seed::init_with(|seed| {
+            let index = Index::Bytes(hash_to_salt(label.as_bytes()));
+            derive_key(seed, index, contexts::TEST_SEED, &[0; SEED_LEN]);
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use const_decoder::Decoder::Hex;
+
+    use super::*;
+
+    #[test]
+    fn test_seed_is_derived_from_zero_key() {
+        const EXPECTED: [u8; 32] =
+            Hex.decode(b"522b4b06715773a48523cc965daf1a74d43b42fbf6963c63952d7a97181a32e0");
+
+        let tree = SecretTree::from_test_seed("alice");
+        assert_eq!(*tree.seed().expose_secret(), EXPECTED);
+    }
+
+    #[test]
+    fn test_seeds_depend_on_label() {
+        let alice = SecretTree::from_test_seed("alice");
+        let bob = SecretTree::from_test_seed("bob");
+        assert_ne!(alice, bob);
+        assert_eq!(alice, SecretTree::from_test_seed("alice"));
+
+        let long_label = "a".repeat(1_000);
+        let tree = SecretTree::from_test_seed(&long_label);
+        assert_ne!(tree, SecretTree::from_test_seed(&long_label[1..]));
+    }
+}
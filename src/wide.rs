@@ -0,0 +1,282 @@
+//! Trees with 64-byte seeds.
+
+use core::{array::TryFromSliceError, convert::TryInto};
+
+#[cfg(feature = "rng")]
+use rand_chacha::ChaChaRng;
+#[cfg(feature = "rng")]
+use rand_core::SeedableRng;
+use rand_core::{CryptoRng, RngCore};
+use secrecy::{zeroize::Zeroize, CloneableSecret, ExposeSecret, ExposeSecretMut, SecretBox};
+
+use crate::{
+    contexts,
+    kdf::{derive_key, try_derive_key, Index, MAX_KEY_LEN},
+    AsByteSliceMut, FillError, Name, SecretTree, Seed,
+};
+
+/// Byte length of a [`Seed64`] (64).
+pub const WIDE_SEED_LEN: usize = MAX_KEY_LEN;
+
+/// Wrapper around seed bytes. Arrays of this size do not implement `Default`, so it is
+/// implemented manually.
+#[derive(Debug, Clone)]
+struct Seed64Bytes([u8; WIDE_SEED_LEN]);
+
+impl Default for Seed64Bytes {
+    fn default() -> Self {
+        Self([0; WIDE_SEED_LEN])
+    }
+}
+
+impl Zeroize for Seed64Bytes {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl CloneableSecret for Seed64Bytes {}
+
+/// Seed for a [`SecretTree64`].
+#[derive(Debug, Clone)]
+pub struct Seed64(SecretBox<Seed64Bytes>);
+
+impl Seed64 {
+    /// Generates a random seed using the provided RNG.
+    pub fn new<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::init_with(|seed_bytes| rng.fill_bytes(seed_bytes))
+    }
+
+    fn init_with(init_fn: impl FnOnce(&mut [u8; WIDE_SEED_LEN])) -> Self {
+        Self(SecretBox::<Seed64Bytes>::init_with_mut(|seed_bytes| {
+            init_fn(&mut seed_bytes.0);
+        }))
+    }
+
+    /// Exposes the bytes contained in this seed.
+    pub fn expose_secret(&self) -> &[u8; WIDE_SEED_LEN] {
+        &self.0.expose_secret().0
+    }
+}
+
+/// Zeroizes the seed bytes in place. This is performed automatically when the seed is dropped.
+impl Zeroize for Seed64 {
+    fn zeroize(&mut self) {
+        self.0.expose_secret_mut().zeroize();
+    }
+}
+
+/// Creates a seed from a (potentially unsecured) byte slice.
+impl From<&[u8; WIDE_SEED_LEN]> for Seed64 {
+    fn from(bytes: &[u8; WIDE_SEED_LEN]) -> Self {
+        Self::init_with(|seed_bytes| {
+            *seed_bytes = *bytes;
+        })
+    }
+}
+
+/// Tree with a 64-byte seed, i.e., the maximum key length supported by Blake2b.
+///
+/// This is a separate, opt-in type for deployments that want full-width seeds; [`SecretTree`]
+/// with its 32-byte seeds remains the default, and its outputs are not affected. `SecretTree64`
+/// provides only the core derivation: [named](Self::child()) and [indexed](Self::index())
+/// children, [secret keys](Self::fill()) and (with the `rng` feature) [CSPRNGs](Self::rng()).
+/// For other functionality, a tree can be [converted](Self::into_tree()) into
+/// a conventional `SecretTree`.
+///
+/// # Derivation
+///
+/// Derivation uses the same salts and personalizations as the corresponding `SecretTree`
+/// methods (see the [crate-level docs](crate#salt-and-personalization)), with the 64-byte seed
+/// as the Blake2b key. Child seeds are 64-byte Blake2b outputs. Since the key length and
+/// the output length are a part of the Blake2b initialization block, outputs never coincide
+/// with the outputs of `SecretTree`, even if the seeds share a prefix.
+///
+/// # Examples
+///
+/// ```
+/// # use secret_tree::{Name, SecretTree64};
+/// # use rand::thread_rng;
+/// let tree = SecretTree64::new(&mut thread_rng());
+/// let mut key = [0_u8; 32];
+/// tree.child(Name::new("signing")).index(0).fill(&mut key);
+/// // Use the conventional tree API for the remaining derivation.
+/// let rng = tree.child(Name::new("nonces")).into_tree().index(3).rng();
+/// ```
+#[derive(Debug)]
+#[must_use = "A tree should generate a secret or child tree"]
+pub struct SecretTree64 {
+    seed: Seed64,
+}
+
+impl SecretTree64 {
+    /// Generates a tree by sampling its seed from the supplied RNG.
+    pub fn new<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::from_seed(Seed64::new(rng))
+    }
+
+    /// Creates a tree from the seed.
+    pub fn from_seed(seed: Seed64) -> Self {
+        Self { seed }
+    }
+
+    /// Restores a tree from the seed specified as a byte slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` has an invalid length (not [`WIDE_SEED_LEN`]).
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, TryFromSliceError> {
+        let seed_ref: &[u8; WIDE_SEED_LEN] = bytes.try_into()?;
+        Ok(Self::from_seed(seed_ref.into()))
+    }
+
+    /// Returns the tree seed.
+    pub fn seed(&self) -> &Seed64 {
+        &self.seed
+    }
+
+    /// Consumes this tree and returns its seed.
+    pub fn into_seed(self) -> Seed64 {
+        self.seed
+    }
+
+    /// Produces a child with the specified string identifier.
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn child(&self, name: Name) -> Self {
+        self.derive_child(Index::Bytes(name.0), contexts::NAME)
+    }
+
+    /// Produces a child with the specified integer index.
+    #[must_use = "derived child tree must be used to produce a secret"]
+    pub fn index(&self, index: u64) -> Self {
+        self.derive_child(Index::Number(index), contexts::INDEX)
+    }
+
+    fn derive_child(&self, index: Index, context: [u8; crate::CONTEXT_LEN]) -> Self {
+        let seed = Seed64::init_with(|child_seed| {
+            derive_key(child_seed, index, context, self.seed.expose_secret());
+        });
+        Self { seed }
+    }
+
+    /// Tries to fill the specified buffer with a key derived from the seed of this tree.
+    /// The same buffers as for [`SecretTree::try_fill()`] are supported.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the buffer does not have length `16..=64` bytes.
+    pub fn try_fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) -> Result<(), FillError> {
+        try_derive_key(
+            dest.as_byte_slice_mut(),
+            Index::None,
+            contexts::FILL_BYTES,
+            self.seed.expose_secret(),
+        )?;
+        dest.convert_to_le();
+        Ok(())
+    }
+
+    /// Fills the specified buffer with a key derived from the seed of this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases when [`Self::try_fill()`] returns an error.
+    #[track_caller]
+    pub fn fill<T: AsByteSliceMut + ?Sized>(self, dest: &mut T) {
+        if let Err(err) = self.try_fill(dest) {
+            panic!("Failed filling a buffer from `SecretTree64`: {err}");
+        }
+    }
+
+    /// Converts this tree into a CSPRNG. The 32-byte RNG seed is derived in the same way
+    /// as in [`SecretTree::rng()`]; thus, the same security considerations apply.
+    #[cfg(feature = "rng")]
+    pub fn rng(self) -> ChaChaRng {
+        let mut seed = <ChaChaRng as SeedableRng>::Seed::default();
+        derive_key(
+            &mut seed,
+            Index::None,
+            contexts::RNG,
+            self.seed.expose_secret(),
+        );
+        let rng = ChaChaRng::from_seed(seed);
+        seed.zeroize();
+        rng
+    }
+
+    /// Converts this tree into a conventional [`SecretTree`] with a 32-byte seed derived
+    /// from the seed of this tree. This allows using the full `SecretTree` API below a certain
+    /// point of the derivation hierarchy, at the cost of the narrower seed.
+    ///
+    /// The seed is derived with the all-zero salt and [`NARROW`](contexts::NARROW)
+    /// personalization.
+    pub fn into_tree(self) -> SecretTree {
+        SecretTree::from_seed(Seed::init_with(|seed| {
+            derive_key(
+                seed,
+                Index::None,
+                contexts::NARROW,
+                self.seed.expose_secret(),
+            );
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use const_decoder::Decoder::Hex;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use super::*;
+
+    fn test_tree() -> SecretTree64 {
+        let seed: [u8; WIDE_SEED_LEN] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+        SecretTree64::from_slice(&seed).unwrap()
+    }
+
+    #[test]
+    fn derivation_reference_vectors() {
+        const KEY: [u8; 32] =
+            Hex.decode(b"efb580e2fd8f7120bf33cc3b8dd2162726e0de0fd4aa0a4e970fec52b457e6f5");
+        const NARROW_SEED: [u8; 32] =
+            Hex.decode(b"74c3affc5ce272c233aaea6d0df57b743cdc3e0b8d1c3b4b5d9a458afaba0a83");
+
+        let mut key = [0_u8; 32];
+        test_tree().index(0).fill(&mut key);
+        assert_eq!(key, KEY);
+        assert_eq!(*test_tree().into_tree().seed().expose_secret(), NARROW_SEED);
+    }
+
+    #[test]
+    fn derivation_is_separated_from_conventional_trees() {
+        let tree = test_tree();
+        let narrow_tree = SecretTree::from_slice(&tree.seed().expose_secret()[..32]).unwrap();
+
+        let mut key = [0_u8; 32];
+        tree.child(Name::new("foo")).fill(&mut key);
+        let mut narrow_key = [0_u8; 32];
+        narrow_tree.child(Name::new("foo")).fill(&mut narrow_key);
+        assert_ne!(key, narrow_key);
+    }
+
+    #[test]
+    fn children_are_distinct() {
+        let tree = SecretTree64::new(&mut ChaChaRng::seed_from_u64(123));
+        let children = [tree.child(Name::new("0")), tree.index(0), tree.index(1)];
+        for (i, child) in children.iter().enumerate() {
+            for other_child in &children[..i] {
+                assert_ne!(
+                    child.seed().expose_secret(),
+                    other_child.seed().expose_secret()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn filling_buffer_with_invalid_len() {
+        let err = test_tree().try_fill(&mut [0_u8; 8]).unwrap_err();
+        assert!(matches!(err, FillError::BufferTooSmall { .. }));
+    }
+}
@@ -0,0 +1,93 @@
+//! Tests checking that derivation outputs do not depend on the crate configuration
+//! (e.g., on whether the `std` feature is enabled). Expected values are hard-coded,
+//! and the tests are run both with default features and without them:
+//!
+//! ```shell
+//! cargo test --no-default-features --test determinism
+//! ```
+
+use const_decoder::Decoder::Hex;
+
+use secret_tree::{Name, SecretTree};
+
+fn tree() -> SecretTree {
+    SecretTree::from_slice(&[7; 32]).unwrap()
+}
+
+#[test]
+fn filling_bytes() {
+    const EXPECTED: [u8; 32] =
+        Hex.decode(b"d2e0786b1187afe03974280878bed7e07ba82167fcbe79bc020207f31a54a5eb");
+
+    let mut output = [0_u8; 32];
+    tree().child(Name::new("bytes")).fill(&mut output);
+    assert_eq!(output, EXPECTED);
+}
+
+#[test]
+fn filling_multibyte_integers() {
+    const EXPECTED: [u8; 16] = Hex.decode(b"f710cbb977bffcfdb3d6836d2578d3a1");
+
+    let mut byte_output = [0_u8; 16];
+    tree().index(1).fill(&mut byte_output);
+    assert_eq!(byte_output, EXPECTED);
+
+    let mut u32_output = [0_u32; 4];
+    tree().index(1).fill(&mut u32_output);
+    let expected_u32 = [0, 1, 2, 3].map(|i| {
+        let chunk = &EXPECTED[i * 4..(i + 1) * 4];
+        u32::from_le_bytes(chunk.try_into().unwrap())
+    });
+    assert_eq!(u32_output, expected_u32);
+
+    let mut u64_output = [0_u64; 2];
+    tree().index(1).fill(&mut u64_output);
+    let expected_u64 = [0, 1].map(|i| {
+        let chunk = &EXPECTED[i * 8..(i + 1) * 8];
+        u64::from_le_bytes(chunk.try_into().unwrap())
+    });
+    assert_eq!(u64_output, expected_u64);
+
+    let mut i128_output = 0_i128;
+    tree().index(1).fill(&mut i128_output);
+    assert_eq!(i128_output, i128::from_le_bytes(EXPECTED));
+}
+
+#[test]
+fn filling_small_buffers() {
+    const EXPECTED: [u8; 10] = Hex.decode(b"06a25d96457645a02cb1");
+
+    let mut output = [0_u16; 5];
+    tree().digest(&[1; 32]).fill_small(&mut output);
+    let expected =
+        [0, 1, 2, 3, 4].map(|i| u16::from_le_bytes([EXPECTED[2 * i], EXPECTED[2 * i + 1]]));
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn filling_with_xof() {
+    const EXPECTED_START: [u8; 16] = Hex.decode(b"364df7577680c1faf1c3601d7f561cab");
+    const EXPECTED_SECOND_BLOCK_START: [u8; 16] = Hex.decode(b"172e3b487cb6e82a7ed0b493b3a67602");
+
+    let mut output = [0_u8; 100];
+    tree().child(Name::new("xof")).fill_xof(&mut output);
+    assert_eq!(output[..16], EXPECTED_START);
+    assert_eq!(output[64..80], EXPECTED_SECOND_BLOCK_START);
+}
+
+#[cfg(feature = "rng")]
+#[test]
+fn rng_output() {
+    const EXPECTED: [u64; 4] = [
+        0x21f1_336d_f581_4a2d,
+        0xbeea_57f7_7775_d7ac,
+        0x53be_c697_d31e_6ad0,
+        0x1675_165b_6515_3679,
+    ];
+
+    use rand::RngCore;
+
+    let mut rng = tree().child(Name::new("rng")).rng();
+    let output = [(); 4].map(|()| rng.next_u64());
+    assert_eq!(output, EXPECTED);
+}
@@ -48,3 +48,9 @@ fn secret_derivation_snapshot() {
     let tree_output = TreeOutput::new(tree);
     assert_yaml_snapshot!("tree-output", tree_output);
 }
+
+#[test]
+fn tree_id_snapshot() {
+    let tree = SecretTree::from_seed(Seed::from(&[7; 32]));
+    assert_yaml_snapshot!("tree-id", tree.id().to_string());
+}
@@ -0,0 +1,177 @@
+//! Property tests checking that alternative derivation APIs produce the same output
+//! as the straightforward chained API.
+
+use proptest::{
+    collection, prop_oneof,
+    strategy::{Just, Strategy},
+    test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+};
+
+use secret_tree::{
+    contexts, AsByteSliceMut, DerivationScript, Name, RawId, SecretTree, Segment, SplitHalf,
+};
+
+/// Fixed RNG seed to make test cases reproducible in CI.
+const RNG_SEED: [u8; 32] = *b"secret-tree derivation stability";
+const MAX_PATH_LEN: usize = 6;
+
+fn test_runner() -> TestRunner {
+    let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &RNG_SEED);
+    TestRunner::new_with_rng(Config::with_cases(256), rng)
+}
+
+fn seed_strategy() -> impl Strategy<Value = [u8; 32]> {
+    proptest::array::uniform32(proptest::num::u8::ANY)
+        .prop_filter("all-zero seed", |seed| seed.iter().any(|&b| b != 0))
+}
+
+fn segment_strategy() -> impl Strategy<Value = Segment> {
+    prop_oneof![
+        // Names starting with a letter are never parsed as indices.
+        "[a-z][a-z0-9_]{0,15}".prop_map(|name| Segment::Name(name.parse().unwrap())),
+        proptest::num::u64::ANY.prop_map(Segment::Index),
+        collection::vec(proptest::num::u8::ANY, 0..=RawId::MAX_LEN)
+            .prop_map(|bytes| Segment::Bytes(RawId::new(&bytes).unwrap())),
+        (proptest::num::u64::ANY, proptest::num::u64::ANY).prop_map(|(a, b)| Segment::Index2(a, b)),
+        proptest::array::uniform32(proptest::num::u8::ANY).prop_map(Segment::Digest),
+        proptest::array::uniform16(proptest::num::u8::ANY).prop_map(Segment::Binding),
+        prop_oneof![Just(SplitHalf::Keys), Just(SplitHalf::Rng)].prop_map(Segment::Split),
+        (0_u32..4).prop_map(Segment::Rotation),
+    ]
+}
+
+fn path_strategy() -> impl Strategy<Value = Vec<Segment>> {
+    collection::vec(segment_strategy(), 0..=MAX_PATH_LEN)
+}
+
+fn derive_chained(tree: &SecretTree, path: &[Segment]) -> SecretTree {
+    let mut tree = SecretTree::from_seed_ref(tree.seed());
+    for segment in path {
+        tree = match *segment {
+            Segment::Name(name) => tree.child(name),
+            Segment::Index(index) => tree.index(index),
+            Segment::Bytes(id) => tree.child_raw(id.as_bytes()).unwrap(),
+            Segment::Index2(a, b) => tree.index2(a, b),
+            Segment::Digest(digest) => tree.digest(&digest),
+            // The associated data cannot be recovered from its hash.
+            Segment::Binding(_) => tree.derive_segment(*segment),
+            Segment::Rotation(generation) => tree.rotate(generation),
+            Segment::Split(half) => {
+                let (keys, rng) = tree.split();
+                match half {
+                    SplitHalf::Keys => keys,
+                    SplitHalf::Rng => rng,
+                }
+            }
+            _ => unreachable!(),
+        };
+    }
+    tree
+}
+
+fn format_path(path: &[Segment]) -> String {
+    let segments: Vec<_> = path.iter().map(ToString::to_string).collect();
+    segments.join("/")
+}
+
+fn fill_output(tree: SecretTree) -> [u8; 32] {
+    let mut output = [0; 32];
+    tree.fill(&mut output);
+    output
+}
+
+#[test]
+fn path_derivation_is_consistent_with_chained_api() {
+    let strategy = (seed_strategy(), path_strategy());
+    test_runner()
+        .run(&strategy, |(seed, path)| {
+            let tree = SecretTree::from_slice(&seed).unwrap();
+            let expected = fill_output(derive_chained(&tree, &path));
+
+            let path_str = format_path(&path);
+            let from_path = tree.derive_path(&path_str).unwrap();
+            assert_eq!(fill_output(from_path), expected, "path: {path_str}");
+
+            let script = DerivationScript::from(path.clone());
+            assert_eq!(fill_output(script.apply(&tree)), expected);
+            let parsed_script: DerivationScript = path_str.parse().unwrap();
+            assert_eq!(parsed_script, script);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn batch_derivation_is_consistent_with_chained_api() {
+    let strategy = (
+        seed_strategy(),
+        path_strategy(),
+        "[a-z]{1,16}",
+        0_u64..1_000,
+    );
+    test_runner()
+        .run(&strategy, |(seed, path, name, start_index)| {
+            let tree = derive_chained(&SecretTree::from_slice(&seed).unwrap(), &path);
+            let name: Name = name.parse().unwrap();
+            let indices = start_index..start_index + 3;
+
+            let children: Vec<_> = tree.indexed_children(name, indices.clone()).collect();
+            let mut buffers = [[0_u64; 4]; 3];
+            let dests = indices
+                .clone()
+                .zip(&mut buffers)
+                .map(|(i, buffer)| (i, buffer as &mut dyn AsByteSliceMut));
+            tree.child(name).fill_many(dests);
+
+            for ((index, child), buffer) in children.into_iter().zip(buffers) {
+                let expected = tree.child(name).index(index);
+                assert_eq!(child, expected);
+                let mut expected_buffer = [0_u64; 4];
+                expected.fill(&mut expected_buffer);
+                assert_eq!(buffer, expected_buffer);
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn raw_derivation_is_consistent_with_filling() {
+    let strategy = (seed_strategy(), path_strategy(), 16_usize..=64);
+    test_runner()
+        .run(&strategy, |(seed, path, len)| {
+            let tree = derive_chained(&SecretTree::from_slice(&seed).unwrap(), &path);
+            let mut expected = vec![0_u8; len];
+            SecretTree::from_seed_ref(tree.seed()).fill(expected.as_mut_slice());
+
+            let mut raw_output = vec![0_u8; len];
+            tree.derive_raw(0, contexts::FILL_BYTES, &mut raw_output);
+            assert_eq!(raw_output, expected);
+
+            let mut multi_output = vec![0_u8; len];
+            tree.multi_deriver()
+                .push(contexts::FILL_BYTES, [0; 16], &mut multi_output)
+                .unwrap();
+            assert_eq!(multi_output, expected);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn digest_chain_is_consistent_with_chained_api() {
+    let digests = collection::vec(
+        proptest::array::uniform32(proptest::num::u8::ANY),
+        0..=MAX_PATH_LEN,
+    );
+    let strategy = (seed_strategy(), digests);
+    test_runner()
+        .run(&strategy, |(seed, digests)| {
+            let tree = SecretTree::from_slice(&seed).unwrap();
+            let path: Vec<_> = digests.iter().copied().map(Segment::Digest).collect();
+            let expected = derive_chained(&tree, &path);
+            assert_eq!(tree.digest_chain(digests), expected);
+            Ok(())
+        })
+        .unwrap();
+}
@@ -0,0 +1,12 @@
+//! UI tests checking compiler diagnostics produced for misuses of the crate API.
+//! Expected diagnostics can be regenerated with:
+//!
+//! ```shell
+//! TRYBUILD=overwrite cargo test --test ui
+//! ```
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
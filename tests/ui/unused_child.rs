@@ -0,0 +1,12 @@
+//! Ignoring a derived child tree is linted against with a specific message.
+
+#![deny(unused_must_use)]
+
+use secret_tree::{Name, SecretTree};
+
+fn main() {
+    let tree = SecretTree::from_slice(&[1; 32]).unwrap();
+    tree.child(Name::new("key"));
+    tree.index(0);
+    tree.digest(&[0; 32]);
+}